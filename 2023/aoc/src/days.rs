@@ -0,0 +1,131 @@
+use aoc_common::{
+    days::{timed, Solution},
+    year::Year,
+};
+
+use crate::{generate, repl};
+
+pub const SOLUTIONS: &[Solution] = &[
+    Solution {
+        day: 1,
+        part: 1,
+        process: |input| Ok(day_01::part1::process(input)?),
+        process_reader: Some(|reader| {
+            Ok(day_01::part1::process_reader(reader, Default::default())?)
+        }),
+        process_timed: None,
+    },
+    Solution {
+        day: 1,
+        part: 2,
+        process: |input| Ok(day_01::part2::process(input)?),
+        process_reader: Some(|reader| {
+            let matcher = day_01::matcher::NumberMatcher::english();
+            Ok(day_01::part2::process_reader(reader, &matcher)?)
+        }),
+        process_timed: None,
+    },
+    Solution {
+        day: 2,
+        part: 1,
+        process: |input| Ok(day_02::part1::process(input)?),
+        process_reader: Some(|reader| Ok(day_02::part1::process_reader(reader)?)),
+        process_timed: Some(|input| {
+            timed(input, day_02::part1::parse, |games| {
+                day_02::part1::solve(games)
+            })
+        }),
+    },
+    Solution {
+        day: 2,
+        part: 2,
+        process: |input| Ok(day_02::part2::process(input)?),
+        process_reader: Some(|reader| Ok(day_02::part2::process_reader(reader)?)),
+        process_timed: Some(|input| {
+            timed(input, day_02::part2::parse, |games| {
+                day_02::part2::solve(games)
+            })
+        }),
+    },
+    Solution {
+        day: 3,
+        part: 1,
+        process: |input| Ok(day_03::part1::process(input)?),
+        process_reader: None,
+        process_timed: Some(|input| {
+            timed(
+                input,
+                |input| Ok(day_03::part1::parse(input)),
+                day_03::part1::solve,
+            )
+        }),
+    },
+    Solution {
+        day: 3,
+        part: 2,
+        process: |input| Ok(day_03::part2::process(input)?),
+        process_reader: None,
+        process_timed: Some(|input| timed(input, day_03::part2::parse, day_03::part2::solve)),
+    },
+    Solution {
+        day: 4,
+        part: 1,
+        process: |input| Ok(day_04::part1::process(input)?),
+        process_reader: Some(|reader| Ok(day_04::part1::process_reader(reader)?)),
+        process_timed: Some(|input| {
+            timed(input, day_04::part1::parse, |cards| {
+                day_04::part1::solve(cards)
+            })
+        }),
+    },
+    Solution {
+        day: 4,
+        part: 2,
+        process: |input| Ok(day_04::part2::process(input)?),
+        process_reader: Some(|reader| Ok(day_04::part2::process_reader(reader)?)),
+        process_timed: Some(|input| timed(input, day_04::part2::parse, day_04::part2::solve)),
+    },
+    Solution {
+        day: 5,
+        part: 1,
+        process: |input| Ok(day_05::part1::process(input)?),
+        process_reader: None,
+        process_timed: Some(|input| timed(input, day_05::part1::parse, day_05::part1::solve)),
+    },
+    Solution {
+        day: 5,
+        part: 2,
+        process: |input| Ok(day_05::part2::process(input)?),
+        process_reader: None,
+        process_timed: Some(|input| timed(input, day_05::part2::parse, day_05::part2::solve)),
+    },
+];
+
+/// Every day that has a crate, with the title of its puzzle.
+pub const TITLES: &[(u8, &str)] = &[
+    (1, day_01::TITLE),
+    (2, day_02::TITLE),
+    (3, day_03::TITLE),
+    (4, day_04::TITLE),
+    (5, day_05::TITLE),
+];
+
+/// The hash of every day crate's sources, for caching their answers.
+pub const SOURCES: &[(u8, &str)] = &[
+    (1, day_01::SOURCE_HASH),
+    (2, day_02::SOURCE_HASH),
+    (3, day_03::SOURCE_HASH),
+    (4, day_04::SOURCE_HASH),
+    (5, day_05::SOURCE_HASH),
+];
+
+/// The days in this workspace, for the runner.
+pub const YEAR: Year = Year {
+    year: 2023,
+    dir: concat!(env!("CARGO_MANIFEST_DIR"), "/.."),
+    solutions: SOLUTIONS,
+    titles: TITLES,
+    sources: SOURCES,
+    generate: Some(|day, seed, knobs, writer| generate::generate(day, seed, knobs, writer)),
+    repl: Some(repl::open),
+};
@@ -0,0 +1,51 @@
+use std::io::Write;
+
+use aoc_common::generate::Knobs;
+use miette::{miette, IntoDiagnostic};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Writes a random input for `day`, the same `seed` always giving the same input.
+pub fn generate<W: Write>(day: u8, seed: u64, knobs: &Knobs, writer: W) -> miette::Result<()> {
+    if knobs.density.is_some() && day != 3 {
+        return Err(miette!("--density only applies to day 3"));
+    }
+
+    let rng = &mut StdRng::seed_from_u64(seed);
+    let result = match day {
+        1 => {
+            let options = knobs
+                .size
+                .map_or_else(Default::default, day_01::generate::Options::with_size);
+            day_01::generate::generate(writer, rng, &options)
+        }
+        2 => {
+            let options = knobs
+                .size
+                .map_or_else(Default::default, day_02::generate::Options::with_size);
+            day_02::generate::generate(writer, rng, &options)
+        }
+        3 => {
+            let mut options = knobs
+                .size
+                .map_or_else(Default::default, day_03::generate::Options::with_size);
+            if let Some(density) = knobs.density {
+                options.density = density;
+            }
+            day_03::generate::generate(writer, rng, &options)
+        }
+        4 => {
+            let options = knobs
+                .size
+                .map_or_else(Default::default, day_04::generate::Options::with_size);
+            day_04::generate::generate(writer, rng, &options)
+        }
+        5 => {
+            let options = knobs
+                .size
+                .map_or_else(Default::default, day_05::generate::Options::with_size);
+            day_05::generate::generate(writer, rng, &options)
+        }
+        _ => return Err(miette!("day {day} has no generator")),
+    };
+    result.into_diagnostic()
+}
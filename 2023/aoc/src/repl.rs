@@ -0,0 +1,37 @@
+use aoc_common::repl::Session;
+use miette::miette;
+
+/// Parses `day`'s input into a session that answers the day's queries.
+pub fn open(day: u8, input: &str) -> miette::Result<Session> {
+    match day {
+        2 => {
+            let session = day_02::repl::Session::parse(input)?;
+            Ok(Session {
+                commands: day_02::repl::COMMANDS,
+                query: Box::new(move |query| Ok(session.query(query)?)),
+            })
+        }
+        3 => {
+            let session = day_03::repl::Session::parse(input)?;
+            Ok(Session {
+                commands: day_03::repl::COMMANDS,
+                query: Box::new(move |query| Ok(session.query(query)?)),
+            })
+        }
+        4 => {
+            let session = day_04::repl::Session::parse(input)?;
+            Ok(Session {
+                commands: day_04::repl::COMMANDS,
+                query: Box::new(move |query| Ok(session.query(query)?)),
+            })
+        }
+        5 => {
+            let session = day_05::repl::Session::parse(input)?;
+            Ok(Session {
+                commands: day_05::repl::COMMANDS,
+                query: Box::new(move |query| Ok(session.query(query)?)),
+            })
+        }
+        _ => Err(miette!("day {day} has no queries")),
+    }
+}
@@ -1,10 +1,11 @@
-use day_01::part1::process;
-use miette::Context;
+use day_01::part1::process_with;
+use miette::{miette, Context};
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
+/// Usage: `part1 [--digits ascii|unicode]`
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     #[cfg(feature = "dhat-heap")]
@@ -13,8 +14,22 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
+    let mut policy = Default::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--digits" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| miette!("--digits needs a value"))?;
+                policy = value.parse().map_err(|e| miette!("{e}"))?;
+            }
+            _ => return Err(miette!("unknown argument `{arg}`")),
+        }
+    }
+
     let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let result = process_with(file, policy).context("process part 1")?;
     println!("{}", result);
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,10 +1,11 @@
-use day_01::part2::process;
-use miette::Context;
+use day_01::{matcher::NumberMatcher, part2::process_with};
+use miette::{miette, Context, IntoDiagnostic};
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
+/// Usage: `part2 [--vocabulary <file>] [--digits ascii|unicode]`
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     #[cfg(feature = "dhat-heap")]
@@ -13,8 +14,32 @@ fn main() -> miette::Result<()> {
     #[cfg(not(feature = "dhat-heap"))]
     tracing_subscriber::fmt::init();
 
+    let mut matcher = NumberMatcher::english();
+    let mut policy = Default::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--vocabulary" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| miette!("--vocabulary needs a file"))?;
+                let config = std::fs::read_to_string(&path)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("read vocabulary {path}"))?;
+                matcher = NumberMatcher::from_config(&config).context("load vocabulary")?;
+            }
+            "--digits" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| miette!("--digits needs a value"))?;
+                policy = value.parse().map_err(|e| miette!("{e}"))?;
+            }
+            _ => return Err(miette!("unknown argument `{arg}`")),
+        }
+    }
+
     let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    let result = process_with(file, &matcher.with_digits(policy)).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
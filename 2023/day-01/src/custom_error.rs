@@ -1,5 +1,4 @@
-
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -7,4 +6,71 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::matcher_error))]
+    MatcherError(#[from] aho_corasick::BuildError),
+
+    #[error("invalid vocabulary entry")]
+    #[diagnostic(
+        code(aoc::invalid_vocabulary),
+        help("vocabulary entries look like `word = value`")
+    )]
+    InvalidVocabulary {
+        #[source_code]
+        src: String,
+        #[label("{reason}")]
+        span: SourceSpan,
+        reason: String,
+    },
+
+    #[error("line {line} has no digit")]
+    #[diagnostic(
+        code(aoc::no_digit),
+        help("every line needs at least one digit to make a calibration value")
+    )]
+    NoDigit {
+        line: usize,
+        #[source_code]
+        src: String,
+        #[label("no digit on this line")]
+        span: SourceSpan,
+    },
+
+    #[error("{operation} overflowed at line {line}")]
+    #[diagnostic(
+        code(aoc::overflow),
+        help("the `u128` feature adds up the calibration values in 128 bits")
+    )]
+    Overflow {
+        operation: &'static str,
+        line: usize,
+        #[source_code]
+        src: String,
+        #[label("this line's value doesn't fit")]
+        span: SourceSpan,
+    },
+}
+
+impl AocError {
+    /// `line` has to be one of the lines of `input`, `index` its 0-based position.
+    pub(crate) fn no_digit(input: &str, index: usize, line: &str) -> Self {
+        let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+        Self::NoDigit {
+            line: index + 1,
+            src: input.to_string(),
+            span: SourceSpan::from((offset, line.len())),
+        }
+    }
+
+    /// Like [`AocError::no_digit`], for an `operation` that overflowed on `line`.
+    pub(crate) fn overflow(input: &str, index: usize, line: &str, operation: &'static str) -> Self {
+        let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+        Self::Overflow {
+            operation,
+            line: index + 1,
+            src: input.to_string(),
+            span: SourceSpan::from((offset, line.len())),
+        }
+    }
+}
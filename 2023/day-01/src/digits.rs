@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+/// The first code point of every run of ten decimal digits (general category `Nd`)
+/// in Unicode 14.0, e.g. `0x0660` for the Arabic-Indic digits `٠`..=`٩`.
+const UNICODE_ZEROS: [u32; 66] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x16A60, 0x16AC0,
+    0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E950, 0x1FBF0,
+];
+
+/// Which characters count as digits when looking for numbers in a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigitPolicy {
+    /// Only `0`..=`9`, like the puzzle expects.
+    #[default]
+    Ascii,
+    /// Any Unicode decimal digit, like `٣` or `７`.
+    Unicode,
+}
+
+impl DigitPolicy {
+    pub fn digit_value(self, c: char) -> Option<u32> {
+        match self {
+            Self::Ascii => c.to_digit(10),
+            Self::Unicode => {
+                let c = u32::from(c);
+                let zero = match UNICODE_ZEROS.binary_search(&c) {
+                    Ok(i) => UNICODE_ZEROS[i],
+                    Err(0) => return None,
+                    Err(i) => UNICODE_ZEROS[i - 1],
+                };
+                (c - zero < 10).then_some(c - zero)
+            }
+        }
+    }
+}
+
+impl FromStr for DigitPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(Self::Ascii),
+            "unicode" => Ok(Self::Unicode),
+            _ => Err(format!(
+                "unknown digit policy `{s}`, expected `ascii` or `unicode`"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_value() {
+        assert_eq!(Some(7), DigitPolicy::Ascii.digit_value('7'));
+        assert_eq!(None, DigitPolicy::Ascii.digit_value('٣'));
+        assert_eq!(Some(3), DigitPolicy::Unicode.digit_value('٣'));
+        assert_eq!(Some(7), DigitPolicy::Unicode.digit_value('７'));
+        assert_eq!(Some(9), DigitPolicy::Unicode.digit_value('9'));
+        assert_eq!(None, DigitPolicy::Unicode.digit_value('a'));
+        assert_eq!(None, DigitPolicy::Unicode.digit_value('½'));
+    }
+}
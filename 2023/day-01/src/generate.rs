@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::matcher::ENGLISH;
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub lines: usize,
+    /// The most characters of filler between two numbers.
+    pub max_gap: usize,
+    /// The most numbers, digits or words, on a single line.
+    pub max_numbers: usize,
+}
+
+impl Options {
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            lines: size,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            lines: 1000,
+            max_gap: 6,
+            max_numbers: 5,
+        }
+    }
+}
+
+/// Writes a calibration document that both parts can solve.
+///
+/// Every line mixes filler letters with digits and spelled out digits, and
+/// holds at least one real digit so part 1 has something to find.
+pub fn generate<W: Write, R: Rng>(mut writer: W, rng: &mut R, options: &Options) -> io::Result<()> {
+    let mut line = String::new();
+    for _ in 0..options.lines {
+        line.clear();
+        let numbers = rng.gen_range(1..=options.max_numbers.max(1));
+        let digit_at = rng.gen_range(0..numbers);
+        for i in 0..numbers {
+            push_filler(&mut line, rng, options.max_gap);
+            if i == digit_at || rng.gen_bool(0.5) {
+                line.push(char::from(b'1' + rng.gen_range(0..9)));
+            } else {
+                let (word, _) = ENGLISH[1..].choose(rng).expect("words to exist");
+                line.push_str(word);
+            }
+        }
+        push_filler(&mut line, rng, options.max_gap);
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+fn push_filler<R: Rng>(line: &mut String, rng: &mut R, max_gap: usize) {
+    for _ in 0..rng.gen_range(0..=max_gap) {
+        line.push(char::from(rng.gen_range(b'a'..=b'z')));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::IntoDiagnostic;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let mut input = vec![];
+        generate(
+            &mut input,
+            &mut StdRng::seed_from_u64(7),
+            &Options::with_size(50),
+        )
+        .into_diagnostic()?;
+        let input = String::from_utf8(input).unwrap();
+
+        assert_eq!(50, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+
+        let mut again = vec![];
+        generate(
+            &mut again,
+            &mut StdRng::seed_from_u64(7),
+            &Options::with_size(50),
+        )
+        .into_diagnostic()?;
+        assert_eq!(input.as_bytes(), again);
+        Ok(())
+    }
+}
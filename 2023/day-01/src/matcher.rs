@@ -0,0 +1,178 @@
+use aho_corasick::AhoCorasick;
+use miette::SourceSpan;
+
+use crate::{custom_error::AocError, digits::DigitPolicy};
+
+/// The words part 2 of the puzzle spells digits with.
+pub const ENGLISH: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// A number found in a line, either as a digit or as one of the vocabulary's words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberMatch {
+    /// Byte offset of the first character of the match.
+    pub start: usize,
+    /// Byte offset right after the last character of the match.
+    pub end: usize,
+    pub value: u32,
+}
+
+/// Finds every number in a line, including ones whose words overlap like `oneight`.
+#[derive(Debug, Clone)]
+pub struct NumberMatcher {
+    automaton: AhoCorasick,
+    values: Vec<u32>,
+    digits: DigitPolicy,
+}
+
+impl NumberMatcher {
+    pub fn new<W: AsRef<str>>(
+        vocabulary: impl IntoIterator<Item = (W, u32)>,
+    ) -> miette::Result<Self, AocError> {
+        let (words, values): (Vec<_>, Vec<_>) = vocabulary.into_iter().unzip();
+        let automaton = AhoCorasick::new(words.iter().map(AsRef::as_ref))?;
+        Ok(Self {
+            automaton,
+            values,
+            digits: DigitPolicy::default(),
+        })
+    }
+
+    /// Changes which characters are recognised as digits.
+    pub fn with_digits(self, digits: DigitPolicy) -> Self {
+        Self { digits, ..self }
+    }
+
+    pub fn english() -> Self {
+        Self::new(ENGLISH).expect("the english vocabulary to be valid")
+    }
+
+    /// Builds a matcher from a vocabulary file.
+    ///
+    /// Every non-empty line that doesn't start with `#` maps a word to its value,
+    /// e.g. `eins = 1`.
+    pub fn from_config(config: &str) -> miette::Result<Self, AocError> {
+        let mut vocabulary = vec![];
+        let mut offset = 0;
+        for line in config.split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+
+            let line = line.trim_end_matches(['\n', '\r']);
+            let invalid = |reason: &str| AocError::InvalidVocabulary {
+                src: config.to_string(),
+                span: SourceSpan::from((line_start, line.len())),
+                reason: reason.to_string(),
+            };
+
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let Some((word, value)) = entry.split_once('=') else {
+                return Err(invalid("missing `=`"));
+            };
+            let word = word.trim();
+            if word.is_empty() {
+                return Err(invalid("empty word"));
+            }
+            let value = value
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| invalid("value is not a number"))?;
+            vocabulary.push((word.to_string(), value));
+        }
+
+        Self::new(vocabulary)
+    }
+
+    /// Returns every digit and vocabulary word in `line`, ordered by position.
+    ///
+    /// Matches may overlap, so `oneight` yields both `one` and `eight`.
+    pub fn find_all(&self, line: &str) -> Vec<NumberMatch> {
+        let digits = line.char_indices().filter_map(|(start, c)| {
+            self.digits.digit_value(c).map(|value| NumberMatch {
+                start,
+                end: start + c.len_utf8(),
+                value,
+            })
+        });
+        let words = self
+            .automaton
+            .find_overlapping_iter(line)
+            .map(|m| NumberMatch {
+                start: m.start(),
+                end: m.end(),
+                value: self.values[m.pattern().as_usize()],
+            });
+
+        let mut matches = digits.chain(words).collect::<Vec<_>>();
+        matches.sort_by_key(|m| (m.start, m.end));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_all() {
+        let matcher = NumberMatcher::english();
+        assert_eq!(
+            vec![
+                NumberMatch {
+                    start: 0,
+                    end: 3,
+                    value: 1
+                },
+                NumberMatch {
+                    start: 2,
+                    end: 7,
+                    value: 8
+                },
+                NumberMatch {
+                    start: 7,
+                    end: 8,
+                    value: 3
+                },
+            ],
+            matcher.find_all("oneight3")
+        );
+    }
+
+    #[test]
+    fn test_from_config() -> miette::Result<()> {
+        let matcher = NumberMatcher::from_config(include_str!("../vocabularies/german.txt"))?;
+        let values = matcher
+            .find_all("zweinsacht")
+            .into_iter()
+            .map(|m| m.value)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2, 1, 8], values);
+
+        let matcher = matcher.with_digits(DigitPolicy::Unicode);
+        let values = matcher
+            .find_all("fünf٣")
+            .into_iter()
+            .map(|m| m.value)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![5, 3], values);
+
+        assert!(matches!(
+            NumberMatcher::from_config("eins = 1\nzwei 2"),
+            Err(AocError::InvalidVocabulary { .. })
+        ));
+        Ok(())
+    }
+}
@@ -1,30 +1,104 @@
-use crate::custom_error::AocError;
+use std::io::BufRead;
+
+use aoc_common::stream::try_for_each_line;
+
+use crate::{custom_error::AocError, digits::DigitPolicy, Sum};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    Ok(input
-        .lines()
-        .flat_map(|line| {
-            let mut digits = line.chars().filter_map(|c| c.to_digit(10));
-            let first = digits.next().expect("at least 1 digit to be present");
-            let second = digits.last().unwrap_or(first);
-            format!("{}{}", first, second).parse::<u32>()
-        })
-        .sum::<u32>()
-        .to_string())
+    process_with(input, DigitPolicy::Ascii)
+}
+
+/// Like [`process`], but lets `policy` decide which characters are digits.
+#[tracing::instrument]
+pub fn process_with(input: &str, policy: DigitPolicy) -> miette::Result<String, AocError> {
+    let mut sum: Sum = 0;
+    for (index, line) in input.lines().enumerate() {
+        let value = calibration_value(line, policy)
+            .ok_or_else(|| AocError::no_digit(input, index, line))?;
+        sum = sum
+            .checked_add(value)
+            .ok_or_else(|| AocError::overflow(input, index, line, SUM))?;
+    }
+    Ok(sum.to_string())
+}
+
+/// Like [`process_with`], but reads the input line by line from `reader`.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader<R: BufRead>(
+    reader: R,
+    policy: DigitPolicy,
+) -> miette::Result<String, AocError> {
+    let mut sum: Sum = 0;
+    try_for_each_line::<_, AocError>(reader, |index, line| {
+        let value =
+            calibration_value(line, policy).ok_or_else(|| AocError::no_digit(line, index, line))?;
+        sum = sum
+            .checked_add(value)
+            .ok_or_else(|| AocError::overflow(line, index, line, SUM))?;
+        Ok(())
+    })?;
+    Ok(sum.to_string())
+}
+
+/// What [`AocError::Overflow`] calls adding up the calibration values.
+pub(crate) const SUM: &str = "the sum of the calibration values";
+
+fn calibration_value(line: &str, policy: DigitPolicy) -> Option<Sum> {
+    let mut digits = line.chars().filter_map(|c| policy.digit_value(c));
+    let first = digits.next()?;
+    let second = digits.next_back().unwrap_or(first);
+    Some(Sum::from(first * 10 + second))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use aoc_common::examples::Example;
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part1-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_reader(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(
+            example.answer,
+            process_reader(example.input.as_bytes(), DigitPolicy::Ascii)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_unicode() -> miette::Result<()> {
+        let input = "a1b٣cd
+é7ü";
+        assert_eq!("88", process(input)?);
+        assert_eq!("90", process_with(input, DigitPolicy::Unicode)?);
+        Ok(())
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
+    fn test_process_no_digit() {
         let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
+pqrstu
 treb7uchet";
-        assert_eq!("142", process(input)?);
-        Ok(())
+        let error = process(input).unwrap_err();
+        assert!(matches!(error, AocError::NoDigit { line: 2, .. }));
+        let error = process_reader(input.as_bytes(), DigitPolicy::Ascii).unwrap_err();
+        assert!(matches!(error, AocError::NoDigit { line: 2, .. }));
     }
 }
@@ -1,66 +1,169 @@
-use itertools::Itertools;
+use std::io::BufRead;
 
-use crate::custom_error::AocError;
+use aoc_common::stream::try_for_each_line;
+
+use crate::{custom_error::AocError, matcher::NumberMatcher, part1, Sum};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    Ok(input
-        .lines()
-        .flat_map(|line| {
-            let (first, second) = process_line(line);
-            format!("{}{}", first, second).parse::<u32>()
-        })
-        .sum::<u32>()
-        .to_string())
+    process_with(input, &NumberMatcher::english())
+}
+
+/// Like [`process`], but spells numbers with the words of any vocabulary.
+#[tracing::instrument(skip(matcher))]
+pub fn process_with(input: &str, matcher: &NumberMatcher) -> miette::Result<String, AocError> {
+    let mut sum: Sum = 0;
+    for (index, line) in input.lines().enumerate() {
+        sum = sum
+            .checked_add(calibration_value(input, index, line, matcher)?)
+            .ok_or_else(|| AocError::overflow(input, index, line, part1::SUM))?;
+    }
+    Ok(sum.to_string())
+}
+
+/// Like [`process_with`], but reads the input line by line from `reader`.
+#[tracing::instrument(skip(reader, matcher))]
+pub fn process_reader<R: BufRead>(
+    reader: R,
+    matcher: &NumberMatcher,
+) -> miette::Result<String, AocError> {
+    let mut sum: Sum = 0;
+    try_for_each_line::<_, AocError>(reader, |index, line| {
+        sum = sum
+            .checked_add(calibration_value(line, index, line, matcher)?)
+            .ok_or_else(|| AocError::overflow(line, index, line, part1::SUM))?;
+        Ok(())
+    })?;
+    Ok(sum.to_string())
 }
 
-fn process_line(line: &str) -> (usize, usize) {
-    const PATTERNS: [&str; 10] = [
-        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
-
-    let line = line.to_string();
-
-    let found_nums = (0..line.len())
-        .filter_map(|i| {
-            let slice = &line[i..];
-            slice
-                .chars()
-                .next()
-                .and_then(|c| c.to_digit(10).map(|n| n as usize))
-                .or_else(|| {
-                    PATTERNS
-                        .iter()
-                        .enumerate()
-                        .find(|(_, pattern)| slice.starts_with(*pattern))
-                        .map(|(num, _)| num)
-                })
-        })
-        .collect_vec();
-
-    let first = found_nums.first().expect("at least 1 digit to be present");
+/// Puts the values of the first and last number on `line` next to each other.
+/// `line` has to be one of the lines of `input`, `index` its 0-based position.
+fn calibration_value(
+    input: &str,
+    index: usize,
+    line: &str,
+    matcher: &NumberMatcher,
+) -> Result<Sum, AocError> {
+    let (first, second) =
+        process_line(line, matcher).ok_or_else(|| AocError::no_digit(input, index, line))?;
+    format!("{}{}", first, second)
+        .parse::<Sum>()
+        .map_err(|_| AocError::overflow(input, index, line, "the calibration value"))
+}
+
+fn process_line(line: &str, matcher: &NumberMatcher) -> Option<(u32, u32)> {
+    let found_nums = matcher.find_all(line);
+
+    let first = found_nums.first()?;
     let second = found_nums.last().unwrap_or(first);
 
-    (*first, *second)
+    Some((first.value, second.value))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use aoc_common::{assert_overflow, examples::Example};
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part2-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_reader(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(
+            example.answer,
+            process_reader(example.input.as_bytes(), &NumberMatcher::english())?
+        );
+        Ok(())
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
+    fn test_process_no_digit() {
         let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        assert_eq!("281", process(input)?);
-
-        let input = "oneight";
-        assert_eq!("18", process(input)?);
+ünë
+xtwone3four";
+        let error = process(input).unwrap_err();
+        assert!(matches!(error, AocError::NoDigit { line: 2, .. }));
+    }
+
+    #[test]
+    fn test_process_overflow() -> miette::Result<()> {
+        let matcher = NumberMatcher::new([("big", u32::MAX)])?;
+        assert_overflow!(
+            process_with("1\nbig1big", &matcher),
+            AocError::Overflow {
+                operation: "the calibration value",
+                line: 2,
+                ..
+            },
+            u128: "42949672954294967306",
+        );
+
+        // the largest calibration value there is, twice
+        let matcher = NumberMatcher::new([("big", u32::MAX / 10)])?;
+        assert_overflow!(
+            process_with("big5\nbig5", &matcher),
+            AocError::Overflow {
+                operation: part1::SUM,
+                line: 2,
+                ..
+            },
+            u128: "8589934590",
+        );
         Ok(())
     }
+
+    /// Looks for a digit or digit word at every position of the line, the way
+    /// part 2 was first solved.
+    fn naive_find_all(line: &str) -> Vec<u32> {
+        const PATTERNS: [&str; 10] = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+
+        (0..line.len())
+            .filter_map(|i| {
+                let slice = &line[i..];
+                slice
+                    .chars()
+                    .next()
+                    .and_then(|c| c.to_digit(10))
+                    .or_else(|| {
+                        PATTERNS
+                            .iter()
+                            .position(|pattern| slice.starts_with(pattern))
+                            .map(|num| num as u32)
+                    })
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_matcher_matches_naive_scan(
+            line in "(zero|one|two|three|four|five|six|seven|eight|nine|[0-9]|[a-z]){0,12}"
+        ) {
+            let found = NumberMatcher::english()
+                .find_all(&line)
+                .into_iter()
+                .map(|m| m.value)
+                .collect::<Vec<_>>();
+            prop_assert_eq!(naive_find_all(&line), found);
+        }
+    }
 }
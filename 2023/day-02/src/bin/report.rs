@@ -0,0 +1,41 @@
+use day_02::report::{process, Bag};
+use miette::{miette, Context};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+/// Prints statistics about every game in the input.
+///
+/// Usage: `report [--csv] [--bag red,green,blue]`
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    #[cfg(not(feature = "dhat-heap"))]
+    tracing_subscriber::fmt::init();
+
+    let mut csv = false;
+    let mut bag = Bag::PUZZLE;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--csv" => csv = true,
+            "--bag" => {
+                let value = args.next().ok_or_else(|| miette!("--bag needs a value"))?;
+                bag = value.parse().map_err(|e| miette!("{e}"))?;
+            }
+            _ => return Err(miette!("unknown argument `{arg}`")),
+        }
+    }
+
+    let file = include_str!("../../input1.txt");
+    let report = process(file, bag).context("process report")?;
+    if csv {
+        print!("{}", report.to_csv());
+    } else {
+        println!("{}", report);
+    }
+    Ok(())
+}
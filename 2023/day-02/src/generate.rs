@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub games: usize,
+    /// The most draws in a single game.
+    pub max_draws: usize,
+    /// The most cubes of one color shown in a single draw.
+    pub max_count: u32,
+}
+
+impl Options {
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            games: size,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            max_draws: 6,
+            max_count: 20,
+        }
+    }
+}
+
+/// Writes one `Game N: ...` line per game, each draw showing one to three
+/// different colors.
+pub fn generate<W: Write, R: Rng>(mut writer: W, rng: &mut R, options: &Options) -> io::Result<()> {
+    let mut colors = ["red", "green", "blue"];
+    for id in 1..=options.games {
+        let draws = (0..rng.gen_range(1..=options.max_draws.max(1)))
+            .map(|_| {
+                colors.shuffle(rng);
+                let shown = rng.gen_range(1..=colors.len());
+                colors[..shown]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.gen_range(1..=options.max_count.max(1))))
+                    .join(", ")
+            })
+            .join("; ");
+        writeln!(writer, "Game {id}: {draws}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::IntoDiagnostic;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let mut input = vec![];
+        generate(
+            &mut input,
+            &mut StdRng::seed_from_u64(7),
+            &Options::with_size(50),
+        )
+        .into_diagnostic()?;
+        let input = String::from_utf8(input).unwrap();
+
+        assert_eq!(50, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+
+        let mut again = vec![];
+        generate(
+            &mut again,
+            &mut StdRng::seed_from_u64(7),
+            &Options::with_size(50),
+        )
+        .into_diagnostic()?;
+        assert_eq!(input.as_bytes(), again);
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
+use std::io::BufRead;
+
+use aoc_common::stream::try_for_each_line;
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::u32, multi::separated_list1,
     sequence::separated_pair, IResult, Parser,
 };
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, Sum};
 
 #[derive(Debug)]
 enum CubeColor {
@@ -36,7 +39,7 @@ impl Cube {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     cube_subsets: Vec<Vec<Cube>>,
 }
@@ -84,29 +87,106 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     ))
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    Ok(input
+/// Parses every line of `input` into a game.
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+    input
         .lines()
-        .map(|line| parse_game(line).expect("input to be properly formatted").1)
+        .map(|line| {
+            parse_game(line)
+                .map(|(_, game)| game)
+                .map_err(|e| AocError::parse_error(input, e))
+        })
+        .collect()
+}
+
+/// Sums the ids of the games that are possible with the cubes in the bag.
+pub fn solve(games: &[Game]) -> miette::Result<String, AocError> {
+    Ok(games
+        .iter()
         .filter(|game| game.is_possible())
-        .map(|game| game.id)
-        .sum::<u32>()
+        .try_fold(0, add_id)?
         .to_string())
 }
 
+fn add_id(sum: Sum, game: &Game) -> Result<Sum, AocError> {
+    sum.checked_add(Sum::from(game.id))
+        .ok_or(AocError::Overflow {
+            operation: SUM,
+            game: game.id,
+        })
+}
+
+/// What [`AocError::Overflow`] calls adding up the ids.
+const SUM: &str = "the sum of the ids";
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the games line by line from `reader`.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader<R: BufRead>(reader: R) -> miette::Result<String, AocError> {
+    let mut sum: Sum = 0;
+    try_for_each_line::<_, AocError>(reader, |_, line| {
+        let (_, game) = parse_game(line).map_err(|e| AocError::parse_error(line, e))?;
+        if game.is_possible() {
+            sum = add_id(sum, &game)?;
+        }
+        Ok(())
+    })?;
+    Ok(sum.to_string())
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use aoc_common::{assert_overflow, examples::Example};
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("8", process(input)?);
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part1-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_reader(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process_reader(example.input.as_bytes())?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 purple";
+        let Err(AocError::ParseError { span, .. }) = parse(input) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(input.find("purple").unwrap(), span.offset());
+    }
+
+    #[test]
+    fn test_process_overflow() {
+        let input = "Game 4294967295: 1 red\nGame 3: 20 red\nGame 1: 2 blue";
+        for result in [process(input), process_reader(input.as_bytes())] {
+            assert_overflow!(
+                result,
+                AocError::Overflow {
+                    operation: SUM,
+                    game: 1
+                },
+                u128: "4294967296",
+            );
+        }
+    }
 }
@@ -1,32 +1,34 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, io::BufRead};
 
+use aoc_common::stream::try_for_each_line;
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::u32, multi::separated_list1,
     sequence::separated_pair, IResult, Parser,
 };
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, Sum};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum CubeColor {
+pub(crate) enum CubeColor {
     Red,
     Green,
     Blue,
 }
 
 #[derive(Debug)]
-struct Cube {
-    color: CubeColor,
-    count: u32,
+pub(crate) struct Cube {
+    pub(crate) color: CubeColor,
+    pub(crate) count: u32,
 }
 
 #[derive(Debug)]
-struct Game {
-    cube_subsets: Vec<Vec<Cube>>,
+pub struct Game {
+    pub(crate) id: u32,
+    pub(crate) cube_subsets: Vec<Vec<Cube>>,
 }
 
 impl Game {
-    fn min_cube_counts(&self) -> HashMap<CubeColor, u32> {
+    pub(crate) fn min_cube_counts(&self) -> HashMap<CubeColor, u32> {
         let mut min_cube_counts = HashMap::new();
 
         for subset in &self.cube_subsets {
@@ -42,8 +44,29 @@ impl Game {
 
         min_cube_counts
     }
+
+    /// The product of the fewest cubes of each color the game needs.
+    fn power(&self) -> Result<Sum, AocError> {
+        self.min_cube_counts()
+            .into_values()
+            .try_fold(1 as Sum, |power, count| power.checked_mul(Sum::from(count)))
+            .ok_or(AocError::Overflow {
+                operation: "the game's power",
+                game: self.id,
+            })
+    }
+}
+
+fn add_power(sum: Sum, game: &Game) -> Result<Sum, AocError> {
+    sum.checked_add(game.power()?).ok_or(AocError::Overflow {
+        operation: SUM,
+        game: game.id,
+    })
 }
 
+/// What [`AocError::Overflow`] calls adding up the powers.
+const SUM: &str = "the sum of the powers";
+
 fn parse_cube_color(input: &str) -> IResult<&str, CubeColor> {
     let (input, color) = alt((tag("red"), tag("green"), tag("blue"))).parse(input)?;
     let color = match color {
@@ -67,38 +90,107 @@ fn parse_cube_subset(input: &str) -> IResult<&str, Vec<Cube>> {
 
 fn parse_game(input: &str) -> IResult<&str, Game> {
     let (input, _) = tag("Game ").parse(input)?;
-    let (input, _game_id) = u32(input)?;
+    let (input, game_id) = u32(input)?;
     let (input, _) = tag(": ").parse(input)?;
     let (input, cube_subsets) = separated_list1(tag("; "), parse_cube_subset)(input)?;
-    Ok((input, Game { cube_subsets }))
+    Ok((
+        input,
+        Game {
+            id: game_id,
+            cube_subsets,
+        },
+    ))
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    Ok(input
+/// Parses every line of `input` into a game.
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+    input
         .lines()
-        .map(|line| parse_game(line).expect("input to be properly formatted").1)
-        .map(|game| {
-            game.min_cube_counts()
-                .into_values()
-                .fold(1, |acc, x| acc * x)
+        .map(|line| {
+            parse_game(line)
+                .map(|(_, game)| game)
+                .map_err(|e| AocError::parse_error(input, e))
         })
-        .sum::<u32>()
-        .to_string())
+        .collect()
+}
+
+/// Sums the power of the smallest bag each game could have been played with.
+pub fn solve(games: &[Game]) -> miette::Result<String, AocError> {
+    Ok(games.iter().try_fold(0, add_power)?.to_string())
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the games line by line from `reader`.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader<R: BufRead>(reader: R) -> miette::Result<String, AocError> {
+    let mut sum: Sum = 0;
+    try_for_each_line::<_, AocError>(reader, |_, line| {
+        let (_, game) = parse_game(line).map_err(|e| AocError::parse_error(line, e))?;
+        sum = add_power(sum, &game)?;
+        Ok(())
+    })?;
+    Ok(sum.to_string())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use aoc_common::{assert_overflow, examples::Example};
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("2286", process(input)?);
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part2-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_reader(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process_reader(example.input.as_bytes())?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_overflow() {
+        // 2000^3 doesn't fit in 32 bits, and 1600^3 only does once
+        let input =
+            "Game 1: 1600 red, 1600 green, 1600 blue\nGame 2: 2000 red, 2000 green, 2000 blue";
+        for result in [process(input), process_reader(input.as_bytes())] {
+            assert_overflow!(
+                result,
+                AocError::Overflow {
+                    operation: "the game's power",
+                    game: 2
+                },
+                u128: "12096000000",
+            );
+        }
+
+        let input =
+            "Game 1: 1600 red, 1600 green, 1600 blue\nGame 2: 1000 red, 1000 green, 1000 blue";
+        for result in [process(input), process_reader(input.as_bytes())] {
+            assert_overflow!(
+                result,
+                AocError::Overflow {
+                    operation: SUM,
+                    game: 2
+                },
+                u128: "5096000000",
+            );
+        }
+    }
 }
@@ -0,0 +1,51 @@
+use crate::{
+    part2::Game,
+    report::{Bag, GameReport},
+};
+
+/// Structured questions about the games, shared by scripts and the REPL.
+pub trait Query {
+    /// How a game went against `bag`.
+    fn game(&self, id: u32, bag: Bag) -> Option<GameReport>;
+
+    /// The ids of every game `bag` could have been used for.
+    fn possible_with(&self, bag: Bag) -> Vec<u32>;
+}
+
+impl Query for [Game] {
+    fn game(&self, id: u32, bag: Bag) -> Option<GameReport> {
+        self.iter()
+            .find(|game| game.id == id)
+            .map(|game| GameReport::new(game, bag))
+    }
+
+    fn possible_with(&self, bag: Bag) -> Vec<u32> {
+        self.iter()
+            .map(|game| GameReport::new(game, bag))
+            .filter(|report| report.feasible)
+            .map(|report| report.id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+    use crate::part2::parse;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        let games = parse(&example.input)?;
+        assert_eq!(vec![1, 2, 5], games.possible_with(Bag::PUZZLE));
+        let game = games.game(3, Bag::PUZZLE).expect("game 3 to exist");
+        assert_eq!(Some(1560), game.min_bag.power());
+        assert!(!game.feasible);
+        assert_eq!(None, games.game(6, Bag::PUZZLE));
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    custom_error::AocError,
+    part2::{self, Game},
+    query::Query,
+    report::{self, Bag},
+};
+
+/// Every query [`Session::query`] answers, with its arguments and what it shows.
+pub const COMMANDS: &[(&str, &str)] = &[
+    (
+        "game <id>",
+        "the fewest cubes a game needs, their power and whether part 1's bag holds them",
+    ),
+    (
+        "possible [red,green,blue]",
+        "every game a bag could have been used for, part 1's bag by default",
+    ),
+];
+
+/// The games, asked about with the bag part 1 asks about.
+pub struct Session {
+    games: Vec<Game>,
+}
+
+impl Session {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        Ok(Self {
+            games: part2::parse(input)?,
+        })
+    }
+
+    pub fn query(&self, query: &str) -> miette::Result<String, AocError> {
+        let invalid = |reason: &str| AocError::InvalidQuery {
+            query: query.to_string(),
+            reason: reason.to_string(),
+        };
+        match query.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["game", id] => {
+                let id = id
+                    .parse::<u32>()
+                    .map_err(|_| invalid("needs a game number"))?;
+                let game = self
+                    .games
+                    .game(id, Bag::PUZZLE)
+                    .ok_or_else(|| invalid("isn't one of the games"))?;
+                let possible = if game.feasible {
+                    "possible"
+                } else {
+                    "impossible"
+                };
+                Ok(format!(
+                    "game {id}: needs at least {} (power {}), {possible} with {}",
+                    game.min_bag,
+                    report::power_text(&game.min_bag),
+                    Bag::PUZZLE
+                ))
+            }
+            ["possible", bag @ ..] if bag.len() <= 1 => {
+                let bag = match bag {
+                    [bag] => bag.parse::<Bag>().map_err(|e| invalid(&e))?,
+                    _ => Bag::PUZZLE,
+                };
+                let ids = self
+                    .games
+                    .possible_with(bag)
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>();
+                Ok(format!(
+                    "{} games are possible with {bag}: {}",
+                    ids.len(),
+                    ids.join(", ")
+                ))
+            }
+            ["game" | "possible", ..] => Err(invalid("has the wrong arguments")),
+            _ => Err(invalid("isn't a query")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        let session = Session::parse(&example.input)?;
+        assert_eq!(
+            "game 3: needs at least 20 red, 13 green, 6 blue (power 1560), impossible with 12 red, 13 green, 14 blue",
+            session.query("game 3")?
+        );
+        assert_eq!(
+            "game 1: needs at least 4 red, 2 green, 6 blue (power 48), possible with 12 red, 13 green, 14 blue",
+            session.query("  game   1 ")?
+        );
+        assert_eq!(
+            "3 games are possible with 12 red, 13 green, 14 blue: 1, 2, 5",
+            session.query("possible")?
+        );
+        assert_eq!(
+            "5 games are possible with 20 red, 13 green, 15 blue: 1, 2, 3, 4, 5",
+            session.query("possible 20,13,15")?
+        );
+        for query in ["game", "game 9", "game three", "possible 1,2", "card 1", ""] {
+            assert!(matches!(
+                session.query(query),
+                Err(AocError::InvalidQuery { .. })
+            ));
+        }
+        Ok(())
+    }
+}
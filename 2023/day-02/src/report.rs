@@ -0,0 +1,349 @@
+use std::{fmt::Display, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::{
+    custom_error::AocError,
+    part2::{parse, CubeColor, Game},
+    Sum,
+};
+
+const COLORS: [CubeColor; 3] = [CubeColor::Red, CubeColor::Green, CubeColor::Blue];
+
+/// A bag holding a fixed amount of cubes of each color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bag {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl Bag {
+    /// The bag the elf asks about in part 1.
+    pub const PUZZLE: Bag = Bag {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
+
+    fn get(&self, color: CubeColor) -> u32 {
+        match color {
+            CubeColor::Red => self.red,
+            CubeColor::Green => self.green,
+            CubeColor::Blue => self.blue,
+        }
+    }
+
+    fn get_mut(&mut self, color: CubeColor) -> &mut u32 {
+        match color {
+            CubeColor::Red => &mut self.red,
+            CubeColor::Green => &mut self.green,
+            CubeColor::Blue => &mut self.blue,
+        }
+    }
+
+    /// The product of the cube counts, `None` when it doesn't fit in a [`Sum`].
+    pub fn power(&self) -> Option<Sum> {
+        // a color the bag has none of makes the power 0, however big the others are
+        if COLORS.into_iter().any(|color| self.get(color) == 0) {
+            return Some(0);
+        }
+        COLORS.into_iter().try_fold(1 as Sum, |power, color| {
+            power.checked_mul(Sum::from(self.get(color)))
+        })
+    }
+
+    /// Whether every draw that `other` is the minimal bag for could have come out of this bag.
+    pub fn contains(&self, other: &Bag) -> bool {
+        COLORS
+            .into_iter()
+            .all(|color| other.get(color) <= self.get(color))
+    }
+}
+
+impl FromStr for Bag {
+    type Err = String;
+
+    /// Parses a bag written as `red,green,blue`, e.g. `12,13,14`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (red, green, blue) = s
+            .split(',')
+            .map(|count| count.trim().parse::<u32>())
+            .collect_tuple()
+            .ok_or_else(|| format!("expected `red,green,blue`, got `{s}`"))?;
+        let parse_error = |e: std::num::ParseIntError| format!("invalid cube count in `{s}`: {e}");
+        Ok(Bag {
+            red: red.map_err(parse_error)?,
+            green: green.map_err(parse_error)?,
+            blue: blue.map_err(parse_error)?,
+        })
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} red, {} green, {} blue",
+            self.red, self.green, self.blue
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameReport {
+    pub id: u32,
+    /// The fewest cubes of each color the bag must have held for this game.
+    pub min_bag: Bag,
+    /// Whether this game is possible with the bag the report was made for.
+    pub feasible: bool,
+}
+
+impl GameReport {
+    pub(crate) fn new(game: &Game, bag: Bag) -> Self {
+        let mut min_bag = Bag::default();
+        for (color, count) in game.min_cube_counts() {
+            *min_bag.get_mut(color) = count;
+        }
+        Self {
+            id: game.id,
+            min_bag,
+            feasible: bag.contains(&min_bag),
+        }
+    }
+}
+
+/// Per-color statistics over every draw in every game.
+///
+/// A color only counts as drawn when it is shown, so the mean is taken over
+/// the draws that mention it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawStats {
+    pub max: Bag,
+    pub mean_red: f64,
+    pub mean_green: f64,
+    pub mean_blue: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub bag: Bag,
+    pub games: Vec<GameReport>,
+    pub draws: DrawStats,
+    /// The smallest single bag that makes every game possible.
+    pub smallest_bag: Bag,
+}
+
+impl Report {
+    pub fn feasible_games(&self) -> impl Iterator<Item = u32> + '_ {
+        self.games
+            .iter()
+            .filter(|game| game.feasible)
+            .map(|game| game.id)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("row,red,green,blue,power,feasible\n");
+        for game in &self.games {
+            let Bag { red, green, blue } = game.min_bag;
+            csv.push_str(&format!(
+                "game {},{red},{green},{blue},{},{}\n",
+                game.id,
+                game.min_bag
+                    .power()
+                    .map_or(String::new(), |power| power.to_string()),
+                game.feasible
+            ));
+        }
+        let Bag { red, green, blue } = self.draws.max;
+        csv.push_str(&format!("max,{red},{green},{blue},,\n"));
+        csv.push_str(&format!(
+            "mean,{:.2},{:.2},{:.2},,\n",
+            self.draws.mean_red, self.draws.mean_green, self.draws.mean_blue
+        ));
+        let Bag { red, green, blue } = self.smallest_bag;
+        csv.push_str(&format!(
+            "smallest bag,{red},{green},{blue},{},\n",
+            self.smallest_bag
+                .power()
+                .map_or(String::new(), |power| power.to_string())
+        ));
+        csv
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<8} {:>6} {:>6} {:>6} {:>8}  feasible",
+            "game", "red", "green", "blue", "power"
+        )?;
+        for game in &self.games {
+            let Bag { red, green, blue } = game.min_bag;
+            writeln!(
+                f,
+                "{:<8} {red:>6} {green:>6} {blue:>6} {:>8}  {}",
+                game.id,
+                power_text(&game.min_bag),
+                if game.feasible { "yes" } else { "no" }
+            )?;
+        }
+        let Bag { red, green, blue } = self.draws.max;
+        writeln!(f, "{:<8} {red:>6} {green:>6} {blue:>6}", "max")?;
+        writeln!(
+            f,
+            "{:<8} {:>6.2} {:>6.2} {:>6.2}",
+            "mean", self.draws.mean_red, self.draws.mean_green, self.draws.mean_blue
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "smallest bag for every game: {} (power {})",
+            self.smallest_bag,
+            power_text(&self.smallest_bag)
+        )?;
+        write!(
+            f,
+            "games possible with {}: {}",
+            self.bag,
+            self.feasible_games().join(", ")
+        )
+    }
+}
+
+/// A bag's power for people to read, which says so when it doesn't fit in a [`Sum`].
+pub(crate) fn power_text(bag: &Bag) -> String {
+    bag.power()
+        .map_or_else(|| "too big".to_string(), |power| power.to_string())
+}
+
+#[tracing::instrument]
+pub fn process(input: &str, bag: Bag) -> miette::Result<Report, AocError> {
+    let games = parse(input)?;
+
+    let game_reports = games
+        .iter()
+        .map(|game| GameReport::new(game, bag))
+        .collect_vec();
+
+    let mut max = Bag::default();
+    let mut totals = [0u64; 3];
+    let mut draws = [0u64; 3];
+    for cube in games.iter().flat_map(|game| &game.cube_subsets).flatten() {
+        let count = max.get_mut(cube.color);
+        *count = (*count).max(cube.count);
+        totals[cube.color as usize] += u64::from(cube.count);
+        draws[cube.color as usize] += 1;
+    }
+    let mean = |color: CubeColor| match draws[color as usize] {
+        0 => 0.0,
+        n => totals[color as usize] as f64 / n as f64,
+    };
+
+    // a bag works for every game exactly when it holds each game's minimal bag,
+    // so the smallest such bag is the per-color maximum over those
+    let smallest_bag = game_reports.iter().fold(Bag::default(), |acc, game| Bag {
+        red: acc.red.max(game.min_bag.red),
+        green: acc.green.max(game.min_bag.green),
+        blue: acc.blue.max(game.min_bag.blue),
+    });
+
+    Ok(Report {
+        bag,
+        games: game_reports,
+        draws: DrawStats {
+            max,
+            mean_red: mean(CubeColor::Red),
+            mean_green: mean(CubeColor::Green),
+            mean_blue: mean(CubeColor::Blue),
+        },
+        smallest_bag,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() -> miette::Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let report = process(input, Bag::PUZZLE)?;
+
+        assert_eq!(vec![1, 2, 5], report.feasible_games().collect_vec());
+        assert_eq!(
+            Bag {
+                red: 4,
+                green: 2,
+                blue: 6
+            },
+            report.games[0].min_bag
+        );
+        assert_eq!(
+            2286,
+            report
+                .games
+                .iter()
+                .filter_map(|game| game.min_bag.power())
+                .sum::<Sum>()
+        );
+        assert_eq!(
+            Bag {
+                red: 20,
+                green: 13,
+                blue: 15
+            },
+            report.smallest_bag
+        );
+        assert_eq!(report.smallest_bag, report.draws.max);
+        assert!(report.to_csv().contains("game 3,20,13,6,1560,false\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_power() {
+        let bag = |count| Bag {
+            red: count,
+            green: count,
+            blue: count,
+        };
+        assert_eq!(Some(2184), Bag::PUZZLE.power());
+        assert_eq!(
+            Some(0),
+            Bag {
+                blue: 0,
+                ..bag(u32::MAX)
+            }
+            .power()
+        );
+        // only fits in 128 bits
+        #[cfg(not(feature = "u128"))]
+        assert_eq!(None, bag(2000).power());
+        #[cfg(feature = "u128")]
+        assert_eq!(Some(8_000_000_000), bag(2000).power());
+    }
+
+    #[cfg(not(feature = "u128"))]
+    #[test]
+    fn test_process_overflow() -> miette::Result<()> {
+        // only the power of game 2 and the smallest bag overflow, not the report
+        let input = "Game 1: 1 red, 2 green, 3 blue\nGame 2: 2000 red, 2000 green, 2000 blue";
+        let report = process(input, Bag::PUZZLE)?;
+        assert_eq!(vec![1], report.feasible_games().collect_vec());
+        assert!(report.to_csv().contains("game 2,2000,2000,2000,,false\n"));
+        assert!(report.to_string().contains("(power too big)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_bag() {
+        assert_eq!(Ok(Bag::PUZZLE), "12,13,14".parse());
+        assert!("12,13".parse::<Bag>().is_err());
+        assert!("12,13,x".parse::<Bag>().is_err());
+    }
+}
@@ -1,5 +1,6 @@
+use std::ops::Range;
 
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -7,4 +8,56 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error("`{query}` {reason}")]
+    #[diagnostic(
+        code(aoc::invalid_query),
+        help("`help` lists every query and its arguments")
+    )]
+    InvalidQuery { query: String, reason: String },
+
+    #[error("{operation} overflowed")]
+    #[diagnostic(
+        code(aoc::overflow),
+        help("the `u128` feature keeps part numbers, gear ratios and their sums in 128 bits")
+    )]
+    Overflow {
+        operation: &'static str,
+        #[source_code]
+        src: String,
+        #[label("this one doesn't fit")]
+        span: SourceSpan,
+    },
+}
+
+impl AocError {
+    /// Points at the `columns` of row `y` of the schematic `grid`.
+    pub(crate) fn overflow(
+        grid: &[Vec<char>],
+        y: usize,
+        columns: Range<usize>,
+        operation: &'static str,
+    ) -> Self {
+        let rows = grid
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        let row_offset = rows[..y].iter().map(|row| row.len() + 1).sum::<usize>();
+        // the columns count characters, the span wants bytes
+        let offset = |x: usize| {
+            row_offset
+                + rows[y]
+                    .char_indices()
+                    .nth(x)
+                    .map_or(rows[y].len(), |(i, _)| i)
+        };
+        Self::Overflow {
+            operation,
+            span: SourceSpan::from((
+                offset(columns.start),
+                offset(columns.end) - offset(columns.start),
+            )),
+            src: rows.join("\n"),
+        }
+    }
+}
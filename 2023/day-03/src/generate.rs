@@ -0,0 +1,115 @@
+use std::io::{self, Write};
+
+use rand::{seq::SliceRandom, Rng};
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// How likely each free cell is to start a number or a symbol, from 0 to 1.
+    pub density: f64,
+}
+
+impl Options {
+    /// A square grid of `size` by `size` cells.
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            width: size,
+            height: size,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            width: 140,
+            height: 140,
+            density: 0.2,
+        }
+    }
+}
+
+/// Writes an engine schematic of `.` cells sprinkled with numbers and symbols.
+///
+/// A third of the placed items are symbols, with `*` being as likely as all the
+/// others together so that there are gears to find. Numbers have up to three
+/// digits and are always followed by a `.` or the end of the row, so two
+/// numbers never run into each other. A density that isn't finite is
+/// rejected, anything else is clamped to 0..=1.
+pub fn generate<W: Write, R: Rng>(mut writer: W, rng: &mut R, options: &Options) -> io::Result<()> {
+    if !options.density.is_finite() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the density has to be a finite number",
+        ));
+    }
+    let density = options.density.clamp(0.0, 1.0);
+    let mut row = String::with_capacity(options.width);
+    for _ in 0..options.height {
+        row.clear();
+        while row.len() < options.width {
+            if !rng.gen_bool(density) {
+                row.push('.');
+            } else if rng.gen_bool(1.0 / 3.0) {
+                let symbol = if rng.gen_bool(0.5) {
+                    '*'
+                } else {
+                    *SYMBOLS.choose(rng).expect("symbols to exist")
+                };
+                row.push(symbol);
+            } else {
+                let digits = rng.gen_range(1..=3).min(options.width - row.len());
+                let number =
+                    rng.gen_range(10usize.pow(digits as u32 - 1)..10usize.pow(digits as u32));
+                row.push_str(&number.to_string());
+                if row.len() < options.width {
+                    row.push('.');
+                }
+            }
+        }
+        writeln!(writer, "{row}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::IntoDiagnostic;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let options = Options {
+            width: 30,
+            height: 20,
+            density: 0.4,
+        };
+        let mut input = vec![];
+        generate(&mut input, &mut StdRng::seed_from_u64(7), &options).into_diagnostic()?;
+        let input = String::from_utf8(input).unwrap();
+
+        assert_eq!(20, input.lines().count());
+        assert!(input.lines().all(|line| line.len() == 30));
+        part1::process(&input)?;
+        part2::process(&input)?;
+
+        let mut again = vec![];
+        generate(&mut again, &mut StdRng::seed_from_u64(7), &options).into_diagnostic()?;
+        assert_eq!(input.as_bytes(), again);
+
+        let options = Options {
+            density: f64::INFINITY,
+            ..options
+        };
+        let error = generate(vec![], &mut StdRng::seed_from_u64(7), &options).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        Ok(())
+    }
+}
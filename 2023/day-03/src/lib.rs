@@ -1,4 +1,20 @@
 pub mod custom_error;
+pub mod generate;
 
 pub mod part1;
 pub mod part2;
+pub mod query;
+pub mod repl;
+
+/// What part numbers and gear ratios are added up in, 128 bits wide with the
+/// `u128` feature.
+#[cfg(not(feature = "u128"))]
+pub type Sum = usize;
+#[cfg(feature = "u128")]
+pub type Sum = u128;
+
+/// The title of the day's puzzle.
+pub const TITLE: &str = env!("CARGO_PKG_DESCRIPTION");
+
+/// A hash of the crate's sources, cached answers are only reused while it stays the same.
+pub const SOURCE_HASH: &str = env!("SOURCE_HASH");
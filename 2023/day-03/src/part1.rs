@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, Sum};
 
 #[derive(Debug)]
 struct Position {
@@ -12,34 +12,33 @@ struct Position {
 struct EngineNumber {
     row: usize,
     range: Range<usize>,
-    number: usize,
+    number: Sum,
 }
 
 #[derive(Debug)]
-struct Engine {
+pub struct Engine {
     grid: Vec<Vec<char>>,
 }
 
 impl Engine {
-    fn get_number(&self, pos: Position) -> Option<EngineNumber> {
+    /// The number with a digit at `pos`, failing when it doesn't fit in a [`Sum`].
+    fn get_number(&self, pos: Position) -> Result<Option<EngineNumber>, AocError> {
         let Position { x, y } = pos;
-        let Some(row) = self.grid.get(y) else {
-            return None;
-        };
-        let Some(val) = row.get(x) else {
-            return None;
+        let Some((row, val)) = self
+            .grid
+            .get(y)
+            .and_then(|row| Some((row, row.get(x)?)))
+            .filter(|(_, val)| val.is_ascii_digit())
+        else {
+            return Ok(None);
         };
 
-        if !val.is_digit(10) {
-            return None;
-        }
-
         let mut num_str = String::new();
 
         // check the left side of the current X position
         let mut left_index = 0;
         for i in (0..x).rev() {
-            let Some(v) = row.get(i).filter(|x| x.is_digit(10)) else {
+            let Some(v) = row.get(i).filter(|x| x.is_ascii_digit()) else {
                 break;
             };
 
@@ -54,7 +53,7 @@ impl Engine {
         // check the right side of the current X position
         let mut right_index = 0;
         for i in (x + 1)..row.len() {
-            let Some(v) = row.get(i).filter(|x| x.is_digit(10)) else {
+            let Some(v) = row.get(i).filter(|x| x.is_ascii_digit()) else {
                 break;
             };
 
@@ -63,31 +62,33 @@ impl Engine {
             num_str.push(*v);
         }
 
-        num_str.parse().ok().map(|num| EngineNumber {
+        let range = (x - left_index)..(x + right_index + 1);
+        // only digits were collected, so parsing only fails when the number is too big
+        let number = num_str
+            .parse()
+            .map_err(|_| AocError::overflow(&self.grid, y, range.clone(), NUMBER))?;
+        Ok(Some(EngineNumber {
             row: y,
-            range: (x - left_index)..(x + right_index + 1),
-            number: num,
-        })
+            range,
+            number,
+        }))
     }
 
-    fn get_numbers(&self) -> Vec<EngineNumber> {
-        self.grid
-            .iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                (0..line.len())
-                    .scan(0, move |x, _idx| {
-                        if let Some(engine_number) = self.get_number(Position { x: *x, y }) {
-                            *x += engine_number.range.end - engine_number.range.start;
-                            Some(Some(engine_number))
-                        } else {
-                            *x += 1;
-                            Some(None)
-                        }
-                    })
-                    .flatten()
-            })
-            .collect::<Vec<_>>()
+    fn get_numbers(&self) -> Result<Vec<EngineNumber>, AocError> {
+        let mut numbers = vec![];
+        for (y, line) in self.grid.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                match self.get_number(Position { x, y })? {
+                    Some(engine_number) => {
+                        x = engine_number.range.end;
+                        numbers.push(engine_number);
+                    }
+                    None => x += 1,
+                }
+            }
+        }
+        Ok(numbers)
     }
 
     fn is_part_number(&self, num: &EngineNumber) -> bool {
@@ -114,8 +115,9 @@ impl Engine {
             self.grid.get(y).is_some_and(|row| {
                 let search_range = num.range.start.saturating_sub(1)
                     ..num.range.end.saturating_add(1).min(row.len());
-                let search_str = &row[search_range.clone()];
-                search_str.iter().any(is_symbol)
+                // rows can be shorter than the number's row, leaving nothing to search
+                row.get(search_range)
+                    .is_some_and(|search_str| search_str.iter().any(is_symbol))
             })
         });
 
@@ -125,8 +127,9 @@ impl Engine {
             self.grid.get(y).is_some_and(|row| {
                 let search_range = num.range.start.saturating_sub(1)
                     ..num.range.end.saturating_add(1).min(row.len());
-                let search_str = &row[search_range.clone()];
-                search_str.iter().any(is_symbol)
+                // rows can be shorter than the number's row, leaving nothing to search
+                row.get(search_range)
+                    .is_some_and(|search_str| search_str.iter().any(is_symbol))
             })
         });
 
@@ -135,39 +138,87 @@ impl Engine {
 }
 
 fn is_symbol(c: &char) -> bool {
-    !c.is_digit(10) && *c != '.'
+    !c.is_ascii_digit() && *c != '.'
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
+/// Reads the engine schematic, any character that isn't a digit or `.` is a symbol.
+pub fn parse(input: &str) -> Engine {
     let grid = input.lines().map(|line| line.chars().collect()).collect();
-    let engine = Engine { grid };
+    Engine { grid }
+}
+
+/// Sums every number that is next to a symbol.
+pub fn solve(engine: &Engine) -> miette::Result<String, AocError> {
     let sum = engine
-        .get_numbers()
+        .get_numbers()?
         .into_iter()
         .filter(|num| engine.is_part_number(num))
-        .map(|num| num.number)
-        .sum::<usize>();
+        .try_fold(0 as Sum, |sum, num| {
+            sum.checked_add(num.number)
+                .ok_or_else(|| AocError::overflow(&engine.grid, num.row, num.range, SUM))
+        })?;
     Ok(sum.to_string())
 }
 
+/// What [`AocError::Overflow`] calls reading a number of the schematic.
+pub(crate) const NUMBER: &str = "the number";
+/// What [`AocError::Overflow`] calls adding up the part numbers.
+const SUM: &str = "the sum of the part numbers";
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::{Path, PathBuf};
+
+    use aoc_common::{assert_overflow, examples::Example};
+    use rstest::rstest;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        assert_eq!("4361", process(input)?);
+    #[rstest]
+    fn test_process(#[files("examples/part1-*.txt")] path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_process_overflow() {
+        let input = "..1\n18446744073709551615#1";
+        assert_overflow!(
+            process(input),
+            AocError::Overflow {
+                operation: SUM,
+                span,
+                ..
+            } if (span.offset(), span.len()) == (input.len() - 1, 1),
+            u128: "18446744073709551616",
+        );
+    }
+
+    #[test]
+    fn test_number_too_big() {
+        // too big even for 128 bits
+        let input = "..\n1234567890123456789012345678901234567890*1";
+        let Err(AocError::Overflow {
+            operation: NUMBER,
+            span,
+            ..
+        }) = process(input)
+        else {
+            panic!("expected the number not to fit");
+        };
+        assert_eq!((3, 40), (span.offset(), span.len()));
+    }
+
+    #[test]
+    fn test_parse_snapshot() {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        let engine = parse(&example.input);
+        insta::assert_debug_snapshot!(engine.get_numbers().unwrap());
+    }
 }
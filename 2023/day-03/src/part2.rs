@@ -2,64 +2,69 @@ use std::ops::Range;
 
 use itertools::Itertools;
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, part1::NUMBER, Sum};
 
 #[derive(Debug)]
-struct Position {
-    x: usize,
-    y: usize,
+pub(crate) struct Position {
+    pub(crate) x: usize,
+    pub(crate) y: usize,
 }
 
 #[derive(Debug)]
 struct EngineNumber {
     row: usize,
     range: Range<usize>,
-    number: usize,
+    number: Sum,
 }
 
 #[derive(Debug)]
-struct EngineGear {
-    pos: Position,
-    part_nums: (usize, usize),
+pub(crate) struct EngineGear {
+    pub(crate) pos: Position,
+    pub(crate) part_nums: (Sum, Sum),
+}
+
+impl EngineGear {
+    /// The product of the two part numbers, `None` when it doesn't fit in a [`Sum`].
+    pub(crate) fn ratio(&self) -> Option<Sum> {
+        self.part_nums.0.checked_mul(self.part_nums.1)
+    }
 }
 
 #[derive(Debug)]
-struct Engine {
+pub struct Engine {
     grid: Vec<Vec<char>>,
     numbers: Vec<EngineNumber>,
     gears: Vec<EngineGear>,
 }
 
 impl Engine {
-    fn new(grid: Vec<Vec<char>>) -> Self {
-        let numbers = Self::get_numbers(&grid);
+    fn new(grid: Vec<Vec<char>>) -> Result<Self, AocError> {
+        let numbers = Self::get_numbers(&grid)?;
         let gears = Self::get_gears(&grid, &numbers);
-        Self {
+        Ok(Self {
             grid,
             numbers,
             gears,
-        }
+        })
     }
 
-    fn get_number(grid: &Vec<Vec<char>>, pos: Position) -> Option<EngineNumber> {
+    /// The number with a digit at `pos`, failing when it doesn't fit in a [`Sum`].
+    fn get_number(grid: &[Vec<char>], pos: Position) -> Result<Option<EngineNumber>, AocError> {
         let Position { x, y } = pos;
-        let Some(row) = grid.get(y) else {
-            return None;
-        };
-        let Some(val) = row.get(x) else {
-            return None;
+        let Some((row, val)) = grid
+            .get(y)
+            .and_then(|row| Some((row, row.get(x)?)))
+            .filter(|(_, val)| val.is_ascii_digit())
+        else {
+            return Ok(None);
         };
 
-        if !val.is_digit(10) {
-            return None;
-        }
-
         let mut num_str = String::new();
 
         // check the left side of the current X position
         let mut left_index = 0;
         for i in (0..x).rev() {
-            let Some(v) = row.get(i).filter(|x| x.is_digit(10)) else {
+            let Some(v) = row.get(i).filter(|x| x.is_ascii_digit()) else {
                 break;
             };
 
@@ -74,7 +79,7 @@ impl Engine {
         // check the right side of the current X position
         let mut right_index = 0;
         for i in (x + 1)..row.len() {
-            let Some(v) = row.get(i).filter(|x| x.is_digit(10)) else {
+            let Some(v) = row.get(i).filter(|x| x.is_ascii_digit()) else {
                 break;
             };
 
@@ -83,35 +88,37 @@ impl Engine {
             num_str.push(*v);
         }
 
-        num_str.parse().ok().map(|num| EngineNumber {
+        let range = (x - left_index)..(x + right_index + 1);
+        // only digits were collected, so parsing only fails when the number is too big
+        let number = num_str
+            .parse()
+            .map_err(|_| AocError::overflow(grid, y, range.clone(), NUMBER))?;
+        Ok(Some(EngineNumber {
             row: y,
-            range: (x - left_index)..(x + right_index + 1),
-            number: num,
-        })
+            range,
+            number,
+        }))
     }
 
-    fn get_numbers(grid: &Vec<Vec<char>>) -> Vec<EngineNumber> {
-        grid.iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                (0..line.len())
-                    .scan(0, move |x, _idx| {
-                        if let Some(engine_number) = Self::get_number(&grid, Position { x: *x, y })
-                        {
-                            *x += engine_number.range.end - engine_number.range.start;
-                            Some(Some(engine_number))
-                        } else {
-                            *x += 1;
-                            Some(None)
-                        }
-                    })
-                    .flatten()
-            })
-            .filter(|num| Self::is_part_number(grid, num))
-            .collect()
+    fn get_numbers(grid: &[Vec<char>]) -> Result<Vec<EngineNumber>, AocError> {
+        let mut numbers = vec![];
+        for (y, line) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                match Self::get_number(grid, Position { x, y })? {
+                    Some(engine_number) => {
+                        x = engine_number.range.end;
+                        numbers.push(engine_number);
+                    }
+                    None => x += 1,
+                }
+            }
+        }
+        numbers.retain(|num| Self::is_part_number(grid, num));
+        Ok(numbers)
     }
 
-    fn is_part_number(grid: &Vec<Vec<char>>, num: &EngineNumber) -> bool {
+    fn is_part_number(grid: &[Vec<char>], num: &EngineNumber) -> bool {
         let y = num.row;
 
         // check if the character to the direct left is a symbol
@@ -134,8 +141,9 @@ impl Engine {
             grid.get(y).is_some_and(|row| {
                 let search_range = num.range.start.saturating_sub(1)
                     ..num.range.end.saturating_add(1).min(row.len());
-                let search_str = &row[search_range.clone()];
-                search_str.iter().any(is_symbol)
+                // rows can be shorter than the number's row, leaving nothing to search
+                row.get(search_range)
+                    .is_some_and(|search_str| search_str.iter().any(is_symbol))
             })
         });
 
@@ -145,15 +153,16 @@ impl Engine {
             grid.get(y).is_some_and(|row| {
                 let search_range = num.range.start.saturating_sub(1)
                     ..num.range.end.saturating_add(1).min(row.len());
-                let search_str = &row[search_range.clone()];
-                search_str.iter().any(is_symbol)
+                // rows can be shorter than the number's row, leaving nothing to search
+                row.get(search_range)
+                    .is_some_and(|search_str| search_str.iter().any(is_symbol))
             })
         });
 
         left || right || top || bottom
     }
 
-    fn get_gears(grid: &Vec<Vec<char>>, nums: &Vec<EngineNumber>) -> Vec<EngineGear> {
+    fn get_gears(grid: &[Vec<char>], nums: &[EngineNumber]) -> Vec<EngineGear> {
         grid.iter()
             .enumerate()
             .flat_map(|(y, row)| {
@@ -202,44 +211,126 @@ impl Engine {
             .collect()
     }
 
-    fn gears(&self) -> &[EngineGear] {
+    pub(crate) fn gears(&self) -> &[EngineGear] {
         &self.gears
     }
+
+    /// The character at `x`, `y`, if the schematic reaches that far.
+    pub(crate) fn char_at(&self, x: usize, y: usize) -> Option<char> {
+        self.grid.get(y)?.get(x).copied()
+    }
+
+    /// An [`AocError::Overflow`] of `operation` pointing at the gear at `x`, `y`.
+    pub(crate) fn overflow(&self, x: usize, y: usize, operation: &'static str) -> AocError {
+        AocError::overflow(&self.grid, y, x..x + 1, operation)
+    }
+
+    /// The part number that has a digit at `x`, `y`.
+    pub(crate) fn part_number_at(&self, x: usize, y: usize) -> Option<Sum> {
+        self.numbers
+            .iter()
+            .find(|num| num.row == y && num.range.contains(&x))
+            .map(|num| num.number)
+    }
 }
 
 fn is_symbol(c: &char) -> bool {
-    !c.is_digit(10) && *c != '.'
+    !c.is_ascii_digit() && *c != '.'
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
+/// Reads the engine schematic and finds its numbers and gears.
+pub fn parse(input: &str) -> miette::Result<Engine, AocError> {
     let grid = input.lines().map(|line| line.chars().collect()).collect();
-    let engine = Engine::new(grid);
-    let gears = engine.gears();
-    let sum = gears
-        .iter()
-        .map(|gear| gear.part_nums.0 * gear.part_nums.1)
-        .sum::<usize>();
+    Engine::new(grid)
+}
+
+/// Sums the gear ratios of every gear.
+pub fn solve(engine: &Engine) -> miette::Result<String, AocError> {
+    let sum = engine.gears().iter().try_fold(0 as Sum, |sum, gear| {
+        let overflow = |operation| engine.overflow(gear.pos.x, gear.pos.y, operation);
+        sum.checked_add(gear.ratio().ok_or_else(|| overflow(RATIO))?)
+            .ok_or_else(|| overflow(SUM))
+    })?;
     Ok(sum.to_string())
 }
 
+/// What [`AocError::Overflow`] calls multiplying a gear's part numbers.
+pub(crate) const RATIO: &str = "the gear's ratio";
+/// What [`AocError::Overflow`] calls adding up the gear ratios.
+const SUM: &str = "the sum of the gear ratios";
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::{Path, PathBuf};
+
+    use aoc_common::{assert_overflow, examples::Example};
+    use rstest::rstest;
+
     use super::*;
 
+    #[rstest]
+    fn test_process(#[files("examples/part2-*.txt")] path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged_rows() -> miette::Result<()> {
+        let input = "..
+...12*4
+.";
+        assert_eq!("48", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_overflow() {
+        assert_overflow!(
+            process("4294967296*4294967296"),
+            AocError::Overflow {
+                operation: RATIO,
+                span,
+                ..
+            } if span.offset() == 10,
+            u128: "18446744073709551616",
+        );
+        // each ratio is just over 2^63
+        let input = "3037000500*3037000500\n.\n3037000500*3037000500";
+        assert_overflow!(
+            process(input),
+            AocError::Overflow {
+                operation: SUM,
+                span,
+                ..
+            } if span.offset() == input.len() - 11,
+            u128: "18446744074000500000",
+        );
+    }
+
+    #[test]
+    fn test_number_too_big() {
+        let input = "2*1234567890123456789012345678901234567890";
+        assert!(matches!(
+            parse(input),
+            Err(AocError::Overflow {
+                operation: NUMBER,
+                ..
+            })
+        ));
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        assert_eq!("467835", process(input)?);
+    fn test_parse_snapshot() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        let engine = parse(&example.input)?;
+        insta::assert_debug_snapshot!("numbers", engine.numbers);
+        insta::assert_debug_snapshot!("gears", engine.gears);
         Ok(())
     }
 }
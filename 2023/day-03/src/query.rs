@@ -0,0 +1,107 @@
+use crate::{
+    part2::{Engine, EngineGear},
+    Sum,
+};
+
+/// What's at one position of the schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub c: char,
+    /// The part number this is a digit of.
+    pub part_number: Option<Sum>,
+    /// The gear this is, if it's a `*` next to exactly two part numbers.
+    pub gear: Option<Gear>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gear {
+    pub x: usize,
+    pub y: usize,
+    pub part_numbers: (Sum, Sum),
+}
+
+impl Gear {
+    /// The product of the two part numbers, `None` when it doesn't fit in a [`Sum`].
+    pub fn ratio(&self) -> Option<Sum> {
+        self.part_numbers.0.checked_mul(self.part_numbers.1)
+    }
+}
+
+impl From<&EngineGear> for Gear {
+    fn from(gear: &EngineGear) -> Self {
+        Self {
+            x: gear.pos.x,
+            y: gear.pos.y,
+            part_numbers: gear.part_nums,
+        }
+    }
+}
+
+/// Structured questions about the schematic, shared by scripts and the REPL.
+pub trait Query {
+    /// What's at column `x` of row `y`, counting from 0.
+    fn cell(&self, x: usize, y: usize) -> Option<Cell>;
+
+    /// Every gear in row `y`, from left to right.
+    fn gears_in_row(&self, y: usize) -> Vec<Gear>;
+}
+
+impl Query for Engine {
+    fn cell(&self, x: usize, y: usize) -> Option<Cell> {
+        Some(Cell {
+            c: self.char_at(x, y)?,
+            part_number: self.part_number_at(x, y),
+            gear: self
+                .gears()
+                .iter()
+                .find(|gear| gear.pos.x == x && gear.pos.y == y)
+                .map(Gear::from),
+        })
+    }
+
+    fn gears_in_row(&self, y: usize) -> Vec<Gear> {
+        self.gears()
+            .iter()
+            .filter(|gear| gear.pos.y == y)
+            .map(Gear::from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+    use crate::part2::parse;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        let engine = parse(&example.input)?;
+        let gear = Gear {
+            x: 3,
+            y: 1,
+            part_numbers: (467, 35),
+        };
+        assert_eq!(
+            Some(Cell {
+                c: '*',
+                part_number: None,
+                gear: Some(gear),
+            }),
+            engine.cell(3, 1)
+        );
+        assert_eq!(
+            Some(467),
+            engine.cell(2, 0).and_then(|cell| cell.part_number)
+        );
+        assert_eq!(None, engine.cell(10, 0));
+        assert_eq!(vec![gear], engine.gears_in_row(1));
+        assert_eq!(Some(16345), gear.ratio());
+        assert!(engine.gears_in_row(0).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    custom_error::AocError,
+    part2,
+    query::{Gear, Query},
+};
+
+/// Every query [`Session::query`] answers, with its arguments and what it shows.
+pub const COMMANDS: &[(&str, &str)] = &[
+    (
+        "cell <x> <y>",
+        "what's at a column and row, counting from 0, and the part number or gear it's part of",
+    ),
+    ("gears <y>", "every gear in a row, with its ratio"),
+];
+
+/// The schematic with its part numbers and gears.
+pub struct Session {
+    engine: part2::Engine,
+}
+
+impl Session {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        Ok(Self {
+            engine: part2::parse(input)?,
+        })
+    }
+
+    pub fn query(&self, query: &str) -> miette::Result<String, AocError> {
+        let invalid = |reason: &str| AocError::InvalidQuery {
+            query: query.to_string(),
+            reason: reason.to_string(),
+        };
+        match query.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["cell", x, y] => {
+                let (Ok(x), Ok(y)) = (x.parse::<usize>(), y.parse::<usize>()) else {
+                    return Err(invalid("needs a column and a row"));
+                };
+                let cell = self
+                    .engine
+                    .cell(x, y)
+                    .ok_or_else(|| invalid("is outside the schematic"))?;
+                let what = match (cell.part_number, cell.gear) {
+                    (Some(number), _) => format!(", in part number {number}"),
+                    (_, Some(gear)) => format!(", {}", self.gear_text(&gear)?),
+                    (None, None) if cell.c.is_ascii_digit() => {
+                        ", in a number that isn't a part number".to_string()
+                    }
+                    (None, None) => String::new(),
+                };
+                Ok(format!("{x},{y}: '{}'{what}", cell.c))
+            }
+            ["gears", y] => {
+                let y = y.parse::<usize>().map_err(|_| invalid("needs a row"))?;
+                let gears = self.engine.gears_in_row(y);
+                if gears.is_empty() {
+                    return Ok(format!("row {y} has no gears"));
+                }
+                Ok(gears
+                    .iter()
+                    .map(|gear| Ok(format!("{},{}: {}", gear.x, gear.y, self.gear_text(gear)?)))
+                    .collect::<Result<Vec<_>, AocError>>()?
+                    .join("\n"))
+            }
+            ["cell" | "gears", ..] => Err(invalid("has the wrong arguments")),
+            _ => Err(invalid("isn't a query")),
+        }
+    }
+
+    fn gear_text(&self, gear: &Gear) -> miette::Result<String, AocError> {
+        let (first, second) = gear.part_numbers;
+        let ratio = gear
+            .ratio()
+            .ok_or_else(|| self.engine.overflow(gear.x, gear.y, part2::RATIO))?;
+        Ok(format!("a gear with ratio {first} * {second} = {ratio}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        let session = Session::parse(&example.input)?;
+        assert_eq!("1,0: '6', in part number 467", session.query("cell 1 0")?);
+        assert_eq!(
+            "3,1: '*', a gear with ratio 467 * 35 = 16345",
+            session.query("cell 3 1")?
+        );
+        assert_eq!(
+            "5,0: '1', in a number that isn't a part number",
+            session.query("cell 5 0")?
+        );
+        assert_eq!("0,1: '.'", session.query("cell 0 1")?);
+        assert_eq!(
+            "5,8: a gear with ratio 755 * 598 = 451490",
+            session.query("gears 8")?
+        );
+        assert_eq!("row 0 has no gears", session.query("gears 0")?);
+        for query in ["cell 1", "cell 10 0", "cell a b", "gears", "game 1"] {
+            assert!(matches!(
+                session.query(query),
+                Err(AocError::InvalidQuery { .. })
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
+use nom::error::ErrorKind;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -7,4 +7,53 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error("couldn't parse the input")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError {
+        #[source_code]
+        src: String,
+        #[label("parsing stopped here ({kind:?})")]
+        span: SourceSpan,
+        kind: ErrorKind,
+    },
+
+    #[error("`{query}` {reason}")]
+    #[diagnostic(
+        code(aoc::invalid_query),
+        help("`help` lists every query and its arguments")
+    )]
+    InvalidQuery { query: String, reason: String },
+
+    #[error("{operation} overflowed at card {card}")]
+    #[diagnostic(
+        code(aoc::overflow),
+        help("the `u128` feature counts points and copies in 128 bits")
+    )]
+    Overflow { operation: &'static str, card: u32 },
+
+    #[error("card {card} comes after card {previous}")]
+    #[diagnostic(
+        code(aoc::unordered_cards),
+        help("reading line by line needs the card ids to increase, `--mode whole` doesn't")
+    )]
+    UnorderedCards { card: u32, previous: u32 },
+}
+
+impl AocError {
+    /// Points at where `error` stopped parsing, which has to be somewhere in `src`.
+    pub(crate) fn parse_error(src: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        let (offset, kind) = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                (e.input.as_ptr() as usize - src.as_ptr() as usize, e.code)
+            }
+            nom::Err::Incomplete(_) => (src.len(), ErrorKind::Complete),
+        };
+        let len = src[offset..].find('\n').unwrap_or(src.len() - offset);
+        Self::ParseError {
+            src: src.to_string(),
+            span: SourceSpan::from((offset, len)),
+            kind,
+        }
+    }
+}
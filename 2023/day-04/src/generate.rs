@@ -0,0 +1,129 @@
+use std::io::{self, Write};
+
+use itertools::Itertools;
+use rand::{
+    seq::{index::sample, SliceRandom},
+    Rng,
+};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub cards: usize,
+    pub winning_numbers: usize,
+    pub received_numbers: usize,
+    /// Numbers are drawn from `1..=max_number`.
+    pub max_number: u32,
+    /// How likely a card is to match any numbers at all, from 0 to 1.
+    pub win_chance: f64,
+    /// The most numbers a single card matches.
+    pub max_matches: usize,
+}
+
+impl Options {
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            cards: size,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            cards: 200,
+            winning_numbers: 10,
+            received_numbers: 25,
+            max_number: 99,
+            win_chance: 0.3,
+            max_matches: 3,
+        }
+    }
+}
+
+/// Writes scratchcards laid out like the puzzle's, with the card ids and
+/// numbers right-aligned in their columns.
+///
+/// Numbers are unique within each list. Fully random cards would match so many
+/// numbers that the copies in part 2 grow exponentially, so instead each card
+/// wins with `win_chance` and then matches up to `max_matches` numbers. As long
+/// as a card matches less than one number on average the copies stay bounded.
+pub fn generate<W: Write, R: Rng>(mut writer: W, rng: &mut R, options: &Options) -> io::Result<()> {
+    let id_width = options.cards.to_string().len();
+    let number_width = options.max_number.to_string().len();
+    let max_number = options.max_number.max(2) as usize;
+    let winning_count = options.winning_numbers.clamp(1, max_number - 1);
+    let received_count = options.received_numbers.clamp(1, max_number);
+    let format = |numbers: &[usize]| {
+        numbers
+            .iter()
+            .map(|n| format!("{:>number_width$}", n + 1))
+            .join(" ")
+    };
+
+    for id in 1..=options.cards {
+        // every number in a random order, the first ones become the winning
+        // numbers and the rest can be received without matching
+        let numbers = sample(rng, max_number, max_number).into_vec();
+        let (winning, others) = numbers.split_at(winning_count);
+
+        let matches = if rng.gen_bool(options.win_chance.clamp(0.0, 1.0)) {
+            rng.gen_range(1..=options.max_matches.max(1))
+        } else {
+            0
+        };
+        let matches = matches
+            .min(winning.len())
+            .min(received_count)
+            .max(received_count.saturating_sub(others.len()));
+        let mut received = winning[..matches]
+            .iter()
+            .chain(&others[..received_count - matches])
+            .copied()
+            .collect_vec();
+        received.shuffle(rng);
+
+        writeln!(
+            writer,
+            "Card {id:>id_width$}: {} | {}",
+            format(winning),
+            format(&received)
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::IntoDiagnostic;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let mut input = vec![];
+        generate(
+            &mut input,
+            &mut StdRng::seed_from_u64(7),
+            &Options::with_size(50),
+        )
+        .into_diagnostic()?;
+        let input = String::from_utf8(input).unwrap();
+
+        assert_eq!(50, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+
+        let mut again = vec![];
+        generate(
+            &mut again,
+            &mut StdRng::seed_from_u64(7),
+            &Options::with_size(50),
+        )
+        .into_diagnostic()?;
+        assert_eq!(input.as_bytes(), again);
+        Ok(())
+    }
+}
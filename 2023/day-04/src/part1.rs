@@ -1,31 +1,52 @@
+use std::io::BufRead;
+
+use aoc_common::stream::try_for_each_line;
 use nom::{
     bytes::complete::tag,
     character::complete::{space0, space1},
     IResult,
 };
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, Count};
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     id: u32,
     winning_numbers: Vec<u32>,
     received_numbers: Vec<u32>,
 }
 
 impl Card {
-    fn points(&self) -> u32 {
-        self.received_numbers
-            .iter()
-            .filter(|n| self.winning_numbers.contains(n))
-            .count()
-            .checked_sub(1)
-            .and_then(|n| n.try_into().ok())
-            .map(|n| 2u32.pow(n))
-            .unwrap_or_default()
+    fn points(&self) -> Result<Count, AocError> {
+        points(
+            self.received_numbers
+                .iter()
+                .filter(|n| self.winning_numbers.contains(n))
+                .count(),
+        )
+        .ok_or(AocError::Overflow {
+            operation: "the card's points",
+            card: self.id,
+        })
+    }
+}
+
+/// What a card with `matching` matching numbers is worth, unless that doesn't fit.
+pub(crate) fn points(matching: usize) -> Option<Count> {
+    match matching.checked_sub(1) {
+        Some(n) => (1 as Count).checked_shl(n.try_into().ok()?),
+        None => Some(0),
     }
 }
 
+/// Adds a card's points to `sum`, failing at the card that doesn't fit.
+fn add_points(sum: Count, card: &Card) -> Result<Count, AocError> {
+    sum.checked_add(card.points()?).ok_or(AocError::Overflow {
+        operation: "the sum of the points",
+        card: card.id,
+    })
+}
+
 fn parse_card(input: &str) -> IResult<&str, Card> {
     let (input, _) = tag("Card")(input)?;
     let (input, _) = space1(input)?;
@@ -52,30 +73,105 @@ fn parse_card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
+/// Parses every line of `input` into a card.
+pub fn parse(input: &str) -> miette::Result<Vec<Card>, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            parse_card(line)
+                .map(|(_, card)| card)
+                .map_err(|e| AocError::parse_error(input, e))
+        })
+        .collect()
+}
+
+/// Sums the points of every card.
+pub fn solve(cards: &[Card]) -> miette::Result<String, AocError> {
+    let points = cards.iter().try_fold(0, add_points)?;
+    Ok(points.to_string())
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let cards = input
-        .lines()
-        .map(|line| parse_card(line).map(|(_, card)| card))
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    let points = cards.iter().map(|card| card.points()).sum::<u32>();
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the cards line by line from `reader`.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader<R: BufRead>(reader: R) -> miette::Result<String, AocError> {
+    let mut points = 0;
+    try_for_each_line::<_, AocError>(reader, |_, line| {
+        let (_, card) = parse_card(line).map_err(|e| AocError::parse_error(line, e))?;
+        points = add_points(points, &card)?;
+        Ok(())
+    })?;
     Ok(points.to_string())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::{Path, PathBuf};
+
+    use aoc_common::examples::Example;
+    use itertools::Itertools;
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part1-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_reader(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process_reader(example.input.as_bytes())?);
+        Ok(())
+    }
+
+    /// A card whose `matching` numbers all win, and one more number that doesn't.
+    fn card(id: usize, matching: usize) -> String {
+        let numbers = (1..=matching).join(" ");
+        format!("Card {id}: {numbers} 1000 | {numbers} 1001")
+    }
+
+    #[test]
+    fn test_process_overflow() -> miette::Result<()> {
+        let bits = Count::BITS as usize;
+        // 2^(bits - 1) points each, so two of them are one more than fits
+        let input = [card(1, bits), card(2, bits)].join("\n");
+        assert_eq!((Count::MAX / 2 + 1).to_string(), process(&card(1, bits))?);
+        for result in [process(&input), process_reader(input.as_bytes())] {
+            assert!(matches!(
+                result,
+                Err(AocError::Overflow {
+                    operation: "the sum of the points",
+                    card: 2
+                })
+            ));
+        }
+        assert!(matches!(
+            process(&card(1, bits + 1)),
+            Err(AocError::Overflow {
+                operation: "the card's points",
+                card: 1
+            })
+        ));
+        Ok(())
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!("13", process(input)?);
+    fn test_parse_snapshot() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        insta::assert_debug_snapshot!(parse(&example.input)?);
         Ok(())
     }
 }
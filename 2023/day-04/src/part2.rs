@@ -1,5 +1,6 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, io::BufRead};
 
+use aoc_common::stream::try_for_each_line;
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
@@ -7,17 +8,17 @@ use nom::{
     IResult,
 };
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, Count};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Card {
-    id: u32,
+pub(crate) struct Card {
+    pub(crate) id: u32,
     winning_numbers: Vec<u32>,
     received_numbers: Vec<u32>,
 }
 
 impl Card {
-    fn matching_numbers(&self) -> Vec<u32> {
+    pub(crate) fn matching_numbers(&self) -> Vec<u32> {
         self.received_numbers
             .iter()
             .filter(|n| self.winning_numbers.contains(n))
@@ -27,40 +28,63 @@ impl Card {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     cards: Vec<Card>,
 }
 
 impl Game {
-    fn final_card_count(&self) -> usize {
+    pub(crate) fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    fn final_card_count(&self) -> Result<Count, AocError> {
+        self.card_counts()?
+            .into_iter()
+            .try_fold(0 as Count, |sum, (card, count)| {
+                sum.checked_add(count).ok_or(AocError::Overflow {
+                    operation: CARDS,
+                    card,
+                })
+            })
+    }
+
+    /// How many of every card there are once every copy was won, by card id.
+    pub(crate) fn card_counts(&self) -> Result<BTreeMap<u32, Count>, AocError> {
         let mut card_counts = self
             .cards
             .iter()
-            .map(|card| (card.id, (card, 1)))
+            .map(|card| (card.id, (card, 1 as Count)))
             .collect::<BTreeMap<_, _>>();
 
-        card_counts
-            .keys()
-            .copied()
-            .collect_vec()
-            .iter()
-            .for_each(|card_id| {
-                card_counts.get(&card_id).copied().map(|(card, count)| {
-                    let matching_numbers = card.matching_numbers().len();
-                    (0..count).for_each(|_| {
-                        (1..=matching_numbers).for_each(|i| {
-                            card_counts.entry(card_id + i as u32).and_modify(|(_, c)| {
-                                *c += 1;
-                            });
-                        })
-                    });
-                });
-            });
+        for card_id in card_counts.keys().copied().collect_vec() {
+            let Some((card, count)) = card_counts.get(&card_id).copied() else {
+                continue;
+            };
+            let matching_numbers = card.matching_numbers().len();
+            for won_id in
+                (1..=matching_numbers).filter_map(|i| card_id.checked_add(i.try_into().ok()?))
+            {
+                if let Some((_, c)) = card_counts.get_mut(&won_id) {
+                    *c = c.checked_add(count).ok_or(AocError::Overflow {
+                        operation: COPIES,
+                        card: won_id,
+                    })?;
+                }
+            }
+        }
 
-        card_counts.values().map(|(_, c)| c).sum()
+        Ok(card_counts
+            .into_iter()
+            .map(|(card_id, (_, c))| (card_id, c))
+            .collect())
     }
 }
 
+/// What [`AocError::Overflow`] calls counting a card's copies.
+const COPIES: &str = "the card's copies";
+/// What [`AocError::Overflow`] calls counting all the cards.
+const CARDS: &str = "the number of cards";
+
 fn parse_card(input: &str) -> IResult<&str, Card> {
     let (input, _) = tag("Card")(input)?;
     let (input, _) = space1(input)?;
@@ -87,30 +111,213 @@ fn parse_card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
+/// Parses every line of `input` into the cards of a game.
+pub fn parse(input: &str) -> miette::Result<Game, AocError> {
     let cards = input
         .lines()
-        .map(|line| parse_card(line).map(|(_, card)| card))
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    let game = Game { cards };
-    Ok(game.final_card_count().to_string())
+        .map(|line| {
+            parse_card(line)
+                .map(|(_, card)| card)
+                .map_err(|e| AocError::parse_error(input, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Game { cards })
+}
+
+/// Counts the cards, including every copy that was won.
+pub fn solve(game: &Game) -> miette::Result<String, AocError> {
+    Ok(game.final_card_count()?.to_string())
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the cards line by line from `reader`.
+///
+/// Instead of keeping every card around, this only remembers how many extra
+/// copies the upcoming card ids have won so far, which is at most as many
+/// entries as the most matching numbers on a single card. That needs the ids to
+/// increase from line to line, but they may skip some, like [`process`] allows.
+#[tracing::instrument(skip(reader))]
+pub fn process_reader<R: BufRead>(reader: R) -> miette::Result<String, AocError> {
+    let mut won_copies = BTreeMap::<u32, Count>::new();
+    let mut previous: Option<u32> = None;
+    let mut card_count: Count = 0;
+    try_for_each_line::<_, AocError>(reader, |_, line| {
+        let (_, card) = parse_card(line).map_err(|e| AocError::parse_error(line, e))?;
+        if let Some(previous) = previous.filter(|&previous| previous >= card.id) {
+            return Err(AocError::UnorderedCards {
+                card: card.id,
+                previous,
+            });
+        }
+        previous = Some(card.id);
+
+        // copies won for ids that were skipped have no card to go to
+        won_copies = won_copies.split_off(&card.id);
+        let overflow = |operation| AocError::Overflow {
+            operation,
+            card: card.id,
+        };
+        let count = won_copies
+            .remove(&card.id)
+            .unwrap_or_default()
+            .checked_add(1)
+            .ok_or_else(|| overflow(COPIES))?;
+        card_count = card_count
+            .checked_add(count)
+            .ok_or_else(|| overflow(CARDS))?;
+
+        let matching_numbers = card.matching_numbers().len();
+        for won_id in (1..=matching_numbers).filter_map(|i| card.id.checked_add(i.try_into().ok()?))
+        {
+            let c = won_copies.entry(won_id).or_default();
+            *c = c.checked_add(count).ok_or(AocError::Overflow {
+                operation: COPIES,
+                card: won_id,
+            })?;
+        }
+        Ok(())
+    })?;
+    Ok(card_count.to_string())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::{Path, PathBuf};
+
+    use aoc_common::examples::Example;
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part2-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_reader(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process_reader(example.input.as_bytes())?);
+        Ok(())
+    }
+
+    /// A handful of cards with small numbers, so that they match now and then
+    /// without the copies growing too big for the copy loop.
+    /// The ids increase but skip one now and then, which copies can't go to.
+    fn cards() -> impl Strategy<Value = String> {
+        let numbers = || prop::collection::vec(1u32..30, 1..6);
+        prop::collection::vec((1u32..3, numbers(), numbers()), 1..12).prop_map(|cards| {
+            let mut id = 0;
+            cards
+                .iter()
+                .map(|(step, winning, received)| {
+                    id += step;
+                    format!(
+                        "Card {id}: {} | {}",
+                        winning.iter().join(" "),
+                        received.iter().join(" ")
+                    )
+                })
+                .join("\n")
+        })
+    }
+
+    /// Hands out every won copy one at a time, the way part 2 was first solved.
+    fn naive_card_count(input: &str) -> usize {
+        let cards = parse(input).unwrap().cards;
+        let mut counts = cards
+            .iter()
+            .map(|card| (card.id, 1))
+            .collect::<BTreeMap<_, usize>>();
+        for card in &cards {
+            for _ in 0..counts[&card.id] {
+                for won_id in card.id + 1..=card.id + card.matching_numbers().len() as u32 {
+                    if let Some(count) = counts.get_mut(&won_id) {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+        counts.values().sum()
+    }
+
+    proptest! {
+        #[test]
+        fn test_process_matches_copy_loop(input in cards()) {
+            let expected = naive_card_count(&input).to_string();
+            prop_assert_eq!(&expected, &process(&input).unwrap());
+            prop_assert_eq!(&expected, &process_reader(input.as_bytes()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_skipped_ids() -> miette::Result<()> {
+        // card 1's copy would go to card 2, which isn't there
+        let input = "Card 1: 1 | 1\nCard 5: 2 | 3";
+        assert_eq!("2", process(input)?);
+        assert_eq!("2", process_reader(input.as_bytes())?);
+
+        let input = "Card 2: 1 | 1\nCard 1: 2 | 3";
+        assert!(matches!(
+            process_reader(input.as_bytes()),
+            Err(AocError::UnorderedCards {
+                card: 1,
+                previous: 2
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> miette::Result<()> {
+        // every card wins a copy of all the cards after it, so card n has 2^(n - 1)
+        // copies and the first n cards 2^n - 1 together
+        let cards = |n: usize| {
+            (1..=n)
+                .map(|id| {
+                    let numbers = (1..=n - id).join(" ");
+                    format!("Card {id}: {numbers} 1000 | {numbers} 1001")
+                })
+                .join("\n")
+        };
+        let bits = Count::BITS as usize;
+        assert_eq!(Count::MAX.to_string(), process(&cards(bits))?);
+        assert_eq!(
+            Count::MAX.to_string(),
+            process_reader(cards(bits).as_bytes())?
+        );
+        let last = u32::try_from(bits + 1).unwrap();
+        for result in [
+            process(&cards(bits + 1)),
+            process_reader(cards(bits + 1).as_bytes()),
+        ] {
+            assert!(matches!(
+                result,
+                Err(AocError::Overflow {
+                    operation: COPIES,
+                    card
+                }) if card == last
+            ));
+        }
+        Ok(())
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!("30", process(input)?);
+    fn test_parse_snapshot() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        insta::assert_debug_snapshot!(parse(&example.input)?);
         Ok(())
     }
 }
@@ -0,0 +1,96 @@
+use crate::{
+    custom_error::AocError,
+    part1,
+    part2::{Card, Game},
+    Count,
+};
+
+/// Everything there is to know about one card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardInfo {
+    pub id: u32,
+    /// The numbers the card has that are winning numbers, in the card's order.
+    pub matching_numbers: Vec<u32>,
+    /// What the card is worth in part 1.
+    pub points: Count,
+    /// How many of the card there are in part 2, the original included.
+    pub copies: Count,
+}
+
+impl CardInfo {
+    fn new(card: &Card, copies: Count) -> Result<Self, AocError> {
+        let matching_numbers = card.matching_numbers();
+        Ok(Self {
+            id: card.id,
+            points: part1::points(matching_numbers.len()).ok_or(AocError::Overflow {
+                operation: "the card's points",
+                card: card.id,
+            })?,
+            matching_numbers,
+            copies,
+        })
+    }
+}
+
+/// Structured questions about the cards, shared by scripts and the REPL. They
+/// fail the way the solutions do when the points or copies overflow.
+pub trait Query {
+    fn card(&self, id: u32) -> Result<Option<CardInfo>, AocError>;
+
+    /// Every card that ends up with more than `copies` copies, by id.
+    fn cards_with_more_copies_than(&self, copies: Count) -> Result<Vec<CardInfo>, AocError>;
+}
+
+impl Query for Game {
+    fn card(&self, id: u32) -> Result<Option<CardInfo>, AocError> {
+        let counts = self.card_counts()?;
+        self.cards()
+            .iter()
+            .find(|card| card.id == id)
+            .map(|card| CardInfo::new(card, counts[&card.id]))
+            .transpose()
+    }
+
+    fn cards_with_more_copies_than(&self, copies: Count) -> Result<Vec<CardInfo>, AocError> {
+        let counts = self.card_counts()?;
+        self.cards()
+            .iter()
+            .filter(|card| counts[&card.id] > copies)
+            .map(|card| CardInfo::new(card, counts[&card.id]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+    use crate::part2::parse;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        let game = parse(&example.input)?;
+        assert_eq!(
+            Some(CardInfo {
+                id: 1,
+                matching_numbers: vec![83, 86, 17, 48],
+                points: 8,
+                copies: 1,
+            }),
+            game.card(1)?
+        );
+        assert_eq!(None, game.card(7)?);
+        assert_eq!(
+            vec![4, 5],
+            game.cards_with_more_copies_than(5)?
+                .iter()
+                .map(|card| card.id)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
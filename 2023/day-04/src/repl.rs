@@ -0,0 +1,115 @@
+use crate::{
+    custom_error::AocError,
+    part2::{self, Game},
+    query::{CardInfo, Query},
+    Count,
+};
+
+/// Every query [`Session::query`] answers, with its arguments and what it shows.
+pub const COMMANDS: &[(&str, &str)] = &[
+    (
+        "card <id>",
+        "a card's matching numbers, the points it's worth and how many copies of it were won",
+    ),
+    (
+        "copies <n>",
+        "every card that ends up with more than n copies",
+    ),
+];
+
+/// The cards, with every copy they win.
+pub struct Session {
+    game: Game,
+}
+
+impl Session {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        Ok(Self {
+            game: part2::parse(input)?,
+        })
+    }
+
+    pub fn query(&self, query: &str) -> miette::Result<String, AocError> {
+        let invalid = |reason: &str| AocError::InvalidQuery {
+            query: query.to_string(),
+            reason: reason.to_string(),
+        };
+        match query.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["card", id] => {
+                let id = id
+                    .parse::<u32>()
+                    .map_err(|_| invalid("needs a card number"))?;
+                let card = self
+                    .game
+                    .card(id)?
+                    .ok_or_else(|| invalid("isn't one of the cards"))?;
+                Ok(card_text(&card))
+            }
+            ["copies", copies] => {
+                let copies = copies
+                    .parse::<Count>()
+                    .map_err(|_| invalid("needs a number of copies"))?;
+                let cards = self.game.cards_with_more_copies_than(copies)?;
+                if cards.is_empty() {
+                    return Ok(format!("no card has more than {copies} copies"));
+                }
+                Ok(cards.iter().map(card_text).collect::<Vec<_>>().join("\n"))
+            }
+            ["card" | "copies", ..] => Err(invalid("has the wrong arguments")),
+            _ => Err(invalid("isn't a query")),
+        }
+    }
+}
+
+fn card_text(card: &CardInfo) -> String {
+    format!(
+        "card {}: {} matching numbers ({}), {} points, {} copies",
+        card.id,
+        card.matching_numbers.len(),
+        card.matching_numbers
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(" "),
+        card.points,
+        card.copies
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        let session = Session::parse(&example.input)?;
+        assert_eq!(
+            "card 1: 4 matching numbers (83 86 17 48), 8 points, 1 copies",
+            session.query("card 1")?
+        );
+        assert_eq!(
+            "card 4: 1 matching numbers (84), 1 points, 8 copies",
+            session.query("card 4")?
+        );
+        assert_eq!(
+            "card 5: 0 matching numbers (), 0 points, 14 copies",
+            session.query("copies 8")?
+        );
+        assert_eq!(
+            "no card has more than 14 copies",
+            session.query("copies 14")?
+        );
+        for query in ["card", "card 7", "card x", "copies", "resolve 1"] {
+            assert!(matches!(
+                session.query(query),
+                Err(AocError::InvalidQuery { .. })
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
+use nom::error::ErrorKind;
 use thiserror::Error;
 
 #[derive(Error, Diagnostic, Debug)]
@@ -7,4 +7,104 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-}
\ No newline at end of file
+
+    #[error("couldn't parse the input")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError {
+        #[source_code]
+        src: String,
+        #[label("parsing stopped here ({kind:?})")]
+        span: SourceSpan,
+        kind: ErrorKind,
+    },
+
+    #[error("`{query}` {reason}")]
+    #[diagnostic(
+        code(aoc::invalid_query),
+        help("`help` lists every query and its arguments")
+    )]
+    InvalidQuery { query: String, reason: String },
+
+    #[error("none of the seeds leads to a location")]
+    #[diagnostic(
+        code(aoc::no_location),
+        help("the almanac needs a seed range that isn't empty and maps from seeds all the way to locations")
+    )]
+    NoLocation,
+
+    #[error("{operation} doesn't fit in 64 bits")]
+    #[diagnostic(
+        code(aoc::overflow),
+        help("a start plus its length has to stay below 2^64")
+    )]
+    Overflow {
+        operation: String,
+        #[source_code]
+        src: String,
+        #[label("this range overflows")]
+        span: SourceSpan,
+    },
+
+    #[error("two source ranges of the {map} map overlap")]
+    #[diagnostic(
+        code(aoc::overlapping_sources),
+        help("the numbers in both ranges would map to two different destinations")
+    )]
+    OverlappingSources {
+        #[source_code]
+        src: String,
+        #[label("this range")]
+        span: SourceSpan,
+        #[label("overlaps this one")]
+        other: SourceSpan,
+        map: String,
+    },
+
+    #[error("two destination ranges of the {map} map overlap")]
+    #[diagnostic(
+        code(aoc::overlapping_destinations),
+        severity(Warning),
+        help("the numbers in both ranges lead back to two sources, only the first is found")
+    )]
+    OverlappingDestinations {
+        #[source_code]
+        src: String,
+        #[label("this range")]
+        span: SourceSpan,
+        #[label("overlaps this one")]
+        other: SourceSpan,
+        map: String,
+    },
+
+    #[error("a range of the {map} map is empty")]
+    #[diagnostic(
+        code(aoc::empty_map_range),
+        severity(Warning),
+        help("a range with length 0 doesn't map anything")
+    )]
+    EmptyMapRange {
+        #[source_code]
+        src: String,
+        #[label("this range")]
+        span: SourceSpan,
+        map: String,
+    },
+}
+
+impl AocError {
+    /// Points at where `error` stopped parsing, which has to be somewhere in `src`.
+    pub(crate) fn parse_error(src: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        let (offset, kind) = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                (e.input.as_ptr() as usize - src.as_ptr() as usize, e.code)
+            }
+            nom::Err::Incomplete(_) => (src.len(), ErrorKind::Complete),
+        };
+        let len = src[offset..].find('\n').unwrap_or(src.len() - offset);
+        Self::ParseError {
+            src: src.to_string(),
+            span: SourceSpan::from((offset, len)),
+            kind,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::io::{self, Write};
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+/// The maps of an almanac, in the order they are chained.
+///
+/// The categories are fixed by the puzzle, so every almanac has these seven maps.
+pub(crate) const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// How many `start length` pairs the `seeds:` line has.
+    pub seed_ranges: usize,
+    /// How many ranges each map has.
+    pub ranges: usize,
+    /// Every map covers the numbers `0..max_value`.
+    pub max_value: u64,
+}
+
+impl Options {
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            ranges: size,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            seed_ranges: 10,
+            ranges: 30,
+            max_value: 1 << 32,
+        }
+    }
+}
+
+/// Writes an almanac whose maps are all one-to-one.
+///
+/// Each map cuts `0..max_value` into `ranges` pieces and lays them out again in
+/// a shuffled order, so no two source or destination ranges overlap and every
+/// location belongs to exactly one seed.
+pub fn generate<W: Write, R: Rng>(mut writer: W, rng: &mut R, options: &Options) -> io::Result<()> {
+    let max_value = options.max_value.max(1);
+
+    let seeds = (0..options.seed_ranges.max(1))
+        .map(|_| {
+            let start = rng.gen_range(0..max_value);
+            let length = rng.gen_range(1..=(max_value - start).min(max_value / 10 + 1));
+            format!("{start} {length}")
+        })
+        .join(" ");
+    writeln!(writer, "seeds: {seeds}")?;
+
+    let ranges = options
+        .ranges
+        .clamp(1, max_value.try_into().unwrap_or(usize::MAX));
+    for map in MAPS {
+        let mut cuts = rand::seq::index::sample(rng, max_value as usize - 1, ranges - 1)
+            .into_iter()
+            .map(|cut| cut as u64 + 1)
+            .collect_vec();
+        cuts.sort_unstable();
+        let pieces = std::iter::once(0)
+            .chain(cuts)
+            .chain(std::iter::once(max_value))
+            .tuple_windows()
+            .map(|(start, end)| (start, end - start))
+            .collect_vec();
+
+        let mut order = (0..pieces.len()).collect_vec();
+        order.shuffle(rng);
+
+        writeln!(writer)?;
+        writeln!(writer, "{map} map:")?;
+        let mut destination = 0;
+        for i in order {
+            let (source, length) = pieces[i];
+            writeln!(writer, "{destination} {source} {length}")?;
+            destination += length;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::IntoDiagnostic;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_generate() -> miette::Result<()> {
+        let options = Options {
+            seed_ranges: 4,
+            ranges: 5,
+            max_value: 1000,
+        };
+        let mut input = vec![];
+        generate(&mut input, &mut StdRng::seed_from_u64(7), &options).into_diagnostic()?;
+        let input = String::from_utf8(input).unwrap();
+
+        assert_eq!(1 + 7 * 7, input.lines().count());
+        part1::process(&input)?;
+        part2::process(&input)?;
+
+        let mut again = vec![];
+        generate(&mut again, &mut StdRng::seed_from_u64(7), &options).into_diagnostic()?;
+        assert_eq!(input.as_bytes(), again);
+        Ok(())
+    }
+}
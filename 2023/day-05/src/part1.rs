@@ -4,27 +4,40 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{multispace1, newline, space1, u64},
+    combinator::map_opt,
     multi::{many1, separated_list1},
     sequence::{separated_pair, tuple},
     IResult, Parser,
 };
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, validate};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, almanac) = parse_almanac(input).unwrap();
+/// Parses the seeds and every map of the almanac, failing on the maps
+/// [`validate`](validate::validate) finds errors in.
+pub fn parse(input: &str) -> miette::Result<Almanac, AocError> {
+    validate::check(input)?;
+    parse_almanac(input)
+        .map(|(_, almanac)| almanac)
+        .map_err(|e| AocError::parse_error(input, e))
+}
 
+/// Finds the lowest location any of the seeds has to be planted at.
+pub fn solve(almanac: &Almanac) -> miette::Result<String, AocError> {
     let lowest_location = almanac
         .seeds
         .iter()
         .filter_map(|&seed| almanac.resolve_for(seed))
         .min()
-        .expect("At least one item to exist.");
+        .ok_or(AocError::NoLocation)?;
 
     Ok(lowest_location.to_string())
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    solve(&parse(input)?)
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum Category {
     Seed,
@@ -96,7 +109,7 @@ impl SourceToDestination {
 }
 
 #[derive(Clone, Debug)]
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<u64>,
     // using a BTreeMap here gives us sorted iteration
     // so we can iterate from the lowest to the highest category
@@ -115,6 +128,10 @@ impl Almanac {
             })
             .find_map(|(c, num)| (c == Category::Location).then_some(num))
     }
+
+    pub(crate) fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
 }
 
 fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
@@ -146,7 +163,12 @@ fn parse_src_to_dst(input: &str) -> IResult<&str, SourceToDestination> {
         multispace1,
         separated_list1(
             newline,
-            separated_list1(space1, u64).map(|v| (v[0]..v[0] + v[2], v[1]..v[1] + v[2])),
+            map_opt(
+                tuple((u64, space1, u64, space1, u64)),
+                |(dst, _, src, _, len)| {
+                    Some((dst..dst.checked_add(len)?, src..src.checked_add(len)?))
+                },
+            ),
         ),
     )
     .map(|(((src, dst), _), ranges)| {
@@ -189,45 +211,36 @@ fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
 
 #[cfg(test)]
 mod tests {
+    use std::path::{Path, PathBuf};
+
+    use aoc_common::examples::Example;
+    use rstest::rstest;
+
     use super::*;
 
+    #[rstest]
+    fn test_process(#[files("examples/part1-*.txt")] path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse("seeds: 79\n\nseed-to-soil map:\n50 98\n"),
+            Err(AocError::ParseError { .. })
+        ));
+        assert!(matches!(
+            parse("seeds: 79\n\nseed-to-soil map:\n18446744073709551615 98 2\n"),
+            Err(AocError::Overflow { .. })
+        ));
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4
-";
-        assert_eq!("35", process(input)?);
+    fn test_parse_snapshot() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        insta::assert_debug_snapshot!(parse(&example.input)?);
         Ok(())
     }
 }
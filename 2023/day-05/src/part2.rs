@@ -1,20 +1,46 @@
 use std::{collections::BTreeMap, fmt::Display, ops::Range, str::FromStr};
 
+use miette::SourceSpan;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{multispace1, newline, space1, u64},
+    combinator::map_opt,
     multi::{many1, separated_list1},
     sequence::{separated_pair, tuple},
     IResult, Parser,
 };
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
-use crate::custom_error::AocError;
+use crate::{custom_error::AocError, validate};
+
+/// Parses the seeds and every map of the almanac, failing on the maps
+/// [`validate`](validate::validate) finds errors in.
+pub fn parse(input: &str) -> miette::Result<Almanac, AocError> {
+    validate::check(input)?;
+    check_seed_ranges(input)?;
+    parse_almanac(input)
+        .map(|(_, almanac)| almanac)
+        .map_err(|e| AocError::parse_error(input, e))
+}
+
+/// Finds the lowest location any seed in the seed ranges has to be planted at.
+pub fn solve(almanac: &Almanac) -> miette::Result<String, AocError> {
+    let lowest_location = almanac.lowest_location().ok_or(AocError::NoLocation)?;
+
+    Ok(lowest_location.to_string())
+}
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (_, almanac) = parse_almanac(input).unwrap();
+    solve(&parse(input)?)
+}
+
+/// Solves part 2 by trying every location from 0 upwards until one leads back
+/// to a seed. Much slower than [`process`], but simple enough to check it against.
+#[tracing::instrument]
+pub fn process_brute_force(input: &str) -> miette::Result<String, AocError> {
+    let almanac = parse(input)?;
 
     let max_location = almanac
         .src_to_dst_maps
@@ -27,7 +53,7 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
         })
         .map(|x| x.source.end)
         .max()
-        .expect("Max location to be found.");
+        .ok_or(AocError::NoLocation)?;
 
     let lowest_location = (0..max_location)
         .into_par_iter()
@@ -39,13 +65,13 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
                     .any(|seed_range| seed_range.contains(&seed))
             })
         })
-        .expect("At least one item to exist.");
+        .ok_or(AocError::NoLocation)?;
 
     Ok(lowest_location.to_string())
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Category {
+pub enum Category {
     Seed,
     Soil,
     Fertilizer,
@@ -104,19 +130,102 @@ struct SourceToDestination {
 }
 
 impl SourceToDestination {
+    fn destination_for(&self, num: u64) -> u64 {
+        for range in &self.ranges {
+            if range.source.contains(&num) {
+                return range.destination.start + (num - range.source.start);
+            }
+        }
+        num
+    }
+
     fn source_for(&self, num: u64) -> u64 {
         for range in &self.ranges {
-            // dbg!(range, num);
             if range.destination.contains(&num) {
                 return range.source.start + (num - range.destination.start);
             }
         }
         num
     }
+
+    /// Maps every number in `range` to its destination, splitting it up wherever
+    /// it crosses the edge of one of the map's ranges.
+    fn destinations_for(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut destinations = vec![];
+        let mut unmapped = vec![range];
+        for category_range in &self.ranges {
+            let CategoryRange {
+                source,
+                destination,
+            } = category_range;
+            unmapped = unmapped
+                .into_iter()
+                .flat_map(|range| {
+                    let start = range.start.max(source.start);
+                    let end = range.end.min(source.end);
+                    if start >= end {
+                        return vec![range];
+                    }
+
+                    destinations.push(
+                        destination.start + (start - source.start)
+                            ..destination.start + (end - source.start),
+                    );
+                    [range.start..start, end..range.end]
+                        .into_iter()
+                        .filter(|rest| !rest.is_empty())
+                        .collect()
+                })
+                .collect();
+        }
+        // numbers that aren't in any range map to themselves
+        destinations.extend(unmapped);
+        destinations
+    }
+
+    /// Every range of numbers that maps into `range`, the inverse of
+    /// [`Self::destinations_for`]. Unlike [`Self::source_for`] it's exact, it also
+    /// finds the numbers that aren't in any range and map to themselves.
+    fn sources_for(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut sources = vec![];
+        let mut unmapped = vec![range.clone()];
+        for CategoryRange {
+            source,
+            destination,
+        } in &self.ranges
+        {
+            let start = range.start.max(destination.start);
+            let end = range.end.min(destination.end);
+            if start < end {
+                sources.push(
+                    source.start + (start - destination.start)
+                        ..source.start + (end - destination.start),
+                );
+            }
+
+            // numbers in a source range map somewhere else, not to themselves
+            unmapped = unmapped
+                .into_iter()
+                .flat_map(|rest| {
+                    let start = rest.start.max(source.start);
+                    let end = rest.end.min(source.end);
+                    if start >= end {
+                        return vec![rest];
+                    }
+                    [rest.start..start, end..rest.end]
+                        .into_iter()
+                        .filter(|rest| !rest.is_empty())
+                        .collect()
+                })
+                .collect();
+        }
+        sources.extend(unmapped);
+        sources
+    }
 }
 
 #[derive(Clone, Debug)]
-struct Almanac {
+pub struct Almanac {
     seed_ranges: Vec<Range<u64>>,
     // using a BTreeMap here gives us sorted iteration
     // so we can iterate from the lowest to the highest category
@@ -131,15 +240,134 @@ impl Almanac {
             .rev()
             .scan(location, |num, (_, map)| {
                 let source_num = map.source_for(*num);
-                // eprintln!(
-                //     "{} -> {}: {} -> {}",
-                //     map.destination, map.source, *num, source_num
-                // );
                 *num = source_num;
                 Some((map.source, source_num))
             })
             .find_map(|(c, num)| (c == Category::Seed).then_some(num))
     }
+
+    /// Every number `seed` maps to on its way to a location, starting with the seed.
+    pub(crate) fn path_for(&self, seed: u64) -> Vec<(Category, u64)> {
+        let mut path = vec![(Category::Seed, seed)];
+        let mut num = seed;
+        for map in self.src_to_dst_maps.values() {
+            num = map.destination_for(num);
+            path.push((map.destination, num));
+        }
+        path
+    }
+
+    /// Every number `location` maps back to on its way to a seed, starting with the location.
+    pub(crate) fn path_back(&self, location: u64) -> Vec<(Category, u64)> {
+        let mut path = vec![(Category::Location, location)];
+        let mut num = location;
+        for map in self.src_to_dst_maps.values().rev() {
+            num = map.source_for(num);
+            path.push((map.source, num));
+        }
+        path
+    }
+
+    /// Whether `seed` is in one of the seed ranges.
+    pub(crate) fn is_planted(&self, seed: u64) -> bool {
+        self.seed_ranges.iter().any(|range| range.contains(&seed))
+    }
+
+    /// Every seed that ends up at one of `locations`, pulling the range back
+    /// through every map.
+    pub(crate) fn seeds_for(&self, locations: Range<u64>) -> Vec<Range<u64>> {
+        merge(
+            self.src_to_dst_maps
+                .values()
+                .rev()
+                .fold(vec![locations], |ranges, map| {
+                    ranges
+                        .into_iter()
+                        .flat_map(|range| map.sources_for(range))
+                        .collect()
+                }),
+        )
+    }
+
+    /// The parts of `seeds` that are in one of the seed ranges.
+    pub(crate) fn planted(&self, seeds: &[Range<u64>]) -> Vec<Range<u64>> {
+        merge(
+            seeds
+                .iter()
+                .flat_map(|seeds| {
+                    self.seed_ranges
+                        .iter()
+                        .map(|range| seeds.start.max(range.start)..seeds.end.min(range.end))
+                })
+                .collect(),
+        )
+    }
+
+    /// Pushes the seed ranges through every map as whole ranges instead of one
+    /// number at a time.
+    fn lowest_location(&self) -> Option<u64> {
+        let seed_ranges = self
+            .seed_ranges
+            .iter()
+            .filter(|range| !range.is_empty())
+            .cloned()
+            .collect::<Vec<_>>();
+        self.src_to_dst_maps
+            .values()
+            .fold(seed_ranges, |ranges, map| {
+                ranges
+                    .into_iter()
+                    .flat_map(|range| map.destinations_for(range))
+                    .collect()
+            })
+            .iter()
+            .map(|range| range.start)
+            .min()
+    }
+}
+
+/// Sorts `ranges` and joins the ones that overlap or touch, dropping the empty ones.
+fn merge(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<u64>> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Fails at the first `start length` pair of the `seeds:` line whose end doesn't
+/// fit in 64 bits, anything that isn't a pair of numbers is left to the parser.
+fn check_seed_ranges(input: &str) -> Result<(), AocError> {
+    let Some(seeds) = input
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("seeds:"))
+    else {
+        return Ok(());
+    };
+    let offset = |s: &str| s.as_ptr() as usize - input.as_ptr() as usize;
+    for pair in seeds.split_whitespace().collect::<Vec<_>>().chunks(2) {
+        let &[start, length] = pair else {
+            break;
+        };
+        let (Ok(a), Ok(b)) = (start.parse::<u64>(), length.parse::<u64>()) else {
+            break;
+        };
+        if a.checked_add(b).is_none() {
+            let end = offset(length) + length.len();
+            return Err(AocError::Overflow {
+                operation: "a seed range".to_string(),
+                src: input.to_string(),
+                span: SourceSpan::from((offset(start), end - offset(start))),
+            });
+        }
+    }
+    Ok(())
 }
 
 fn parse_seed_ranges(input: &str) -> IResult<&str, Vec<Range<u64>>> {
@@ -148,7 +376,9 @@ fn parse_seed_ranges(input: &str) -> IResult<&str, Vec<Range<u64>>> {
         space1,
         separated_list1(
             space1,
-            separated_pair(u64, space1, u64).map(|(a, b)| a..a + b),
+            map_opt(separated_pair(u64, space1, u64), |(a, b)| {
+                Some(a..a.checked_add(b)?)
+            }),
         ),
     )
     .map(|(_, seed_ranges)| seed_ranges)
@@ -178,7 +408,12 @@ fn parse_src_to_dst(input: &str) -> IResult<&str, SourceToDestination> {
         multispace1,
         separated_list1(
             newline,
-            separated_list1(space1, u64).map(|v| (v[0]..v[0] + v[2], v[1]..v[1] + v[2])),
+            map_opt(
+                tuple((u64, space1, u64, space1, u64)),
+                |(dst, _, src, _, len)| {
+                    Some((dst..dst.checked_add(len)?, src..src.checked_add(len)?))
+                },
+            ),
         ),
     )
     .map(|(((src, dst), _), ranges)| {
@@ -221,45 +456,139 @@ fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
 
 #[cfg(test)]
 mod tests {
+    use std::path::{Path, PathBuf};
+
+    use aoc_common::examples::Example;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use rstest::rstest;
+    use rstest_reuse::{self, *};
+
     use super::*;
 
+    #[template]
+    #[rstest]
+    fn examples(#[files("examples/part2-*.txt")] path: PathBuf) {}
+
+    #[apply(examples)]
+    fn test_process(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+
+    #[apply(examples)]
+    fn test_process_brute_force(path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process_brute_force(&example.input)?);
+        Ok(())
+    }
+
+    /// An almanac whose maps each cut `0..max_value` into pieces and lay them out
+    /// again in a shuffled order, so every location leads back to exactly one seed
+    /// like in the real input. It's built from the ranges themselves rather than
+    /// a seeded generator, so that proptest can shrink a failing almanac.
+    fn almanac() -> impl Strategy<Value = String> {
+        (2u64..200)
+            .prop_flat_map(|max_value| {
+                let seed_range =
+                    (0..max_value).prop_flat_map(move |start| (Just(start), 1..=max_value - start));
+                let map =
+                    prop::collection::btree_set(1..max_value, 0..6).prop_flat_map(move |cuts| {
+                        let pieces = std::iter::once(0)
+                            .chain(cuts)
+                            .chain(std::iter::once(max_value))
+                            .tuple_windows()
+                            .map(|(start, end)| (start, end - start))
+                            .collect_vec();
+                        let order = (0..pieces.len()).collect_vec();
+                        (Just(pieces), Just(order).prop_shuffle())
+                    });
+                (
+                    prop::collection::vec(seed_range, 1..4),
+                    prop::collection::vec(map, crate::generate::MAPS.len()),
+                )
+            })
+            .prop_map(|(seeds, maps)| {
+                let mut input = format!(
+                    "seeds: {}\n",
+                    seeds
+                        .iter()
+                        .map(|(start, length)| format!("{start} {length}"))
+                        .join(" ")
+                );
+                for (name, (pieces, order)) in crate::generate::MAPS.iter().zip(maps) {
+                    input.push_str(&format!("\n{name} map:\n"));
+                    let mut destination = 0;
+                    for i in order {
+                        let (source, length) = pieces[i];
+                        input.push_str(&format!("{destination} {source} {length}\n"));
+                        destination += length;
+                    }
+                }
+                input
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_process_matches_brute_force(input in almanac()) {
+            prop_assert_eq!(process_brute_force(&input).unwrap(), process(&input).unwrap());
+        }
+
+        #[test]
+        fn test_seeds_for_matches_path_for(input in almanac(), start in 0u64..200, length in 0u64..50) {
+            let almanac = parse(&input).unwrap();
+            let locations = start..start + length;
+            let seeds = almanac.seeds_for(locations.clone());
+            for seed in 0..250 {
+                let (_, location) = *almanac.path_for(seed).last().unwrap();
+                prop_assert_eq!(
+                    locations.contains(&location),
+                    seeds.iter().any(|seeds| seeds.contains(&seed)),
+                    "seed {} reaches location {}", seed, location
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_seed_range_overflow() {
+        let input = "seeds: 79 14 18446744073709551615 2\n\nseed-to-soil map:\n50 98 2\n";
+        let Err(AocError::Overflow {
+            operation, span, ..
+        }) = parse(input)
+        else {
+            panic!("the second seed range to overflow");
+        };
+        assert_eq!("a seed range", operation);
+        assert_eq!(
+            "18446744073709551615 2",
+            &input[span.offset()..span.offset() + span.len()]
+        );
+    }
+
+    #[test]
+    fn test_no_location() {
+        let input = "seeds: 1 0\n\nseed-to-soil map:\n1 2 3\n";
+        assert!(matches!(process(input), Err(AocError::NoLocation)));
+        assert!(matches!(
+            process_brute_force(input),
+            Err(AocError::NoLocation)
+        ));
+
+        // without a map to locations there is nothing for brute force to search
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n";
+        assert!(matches!(
+            process_brute_force(input),
+            Err(AocError::NoLocation)
+        ));
+    }
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4
-";
-        assert_eq!("46", process(input)?);
+    fn test_parse_snapshot() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        insta::assert_debug_snapshot!(parse(&example.input)?);
         Ok(())
     }
 }
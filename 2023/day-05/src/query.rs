@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+use crate::part2::{Almanac, Category};
+
+/// Structured questions about the almanac, shared by scripts and the REPL.
+pub trait Query {
+    /// Every number `seed` maps to on its way to a location, starting with the seed.
+    fn resolve(&self, seed: u64) -> Vec<(Category, u64)>;
+
+    /// Every number `location` maps back to on its way to a seed, starting with the location.
+    fn locate(&self, location: u64) -> Vec<(Category, u64)>;
+
+    /// Whether `seed` is in one of the seed ranges.
+    fn is_planted(&self, seed: u64) -> bool;
+
+    /// Every seed that ends up at one of `locations`, as sorted ranges that don't
+    /// touch, whether it's in a seed range or not.
+    fn seeds_located_in(&self, locations: Range<u64>) -> Vec<Range<u64>>;
+
+    /// Like [`Query::seeds_located_in`], but only the seeds in a seed range.
+    fn planted_seeds_located_in(&self, locations: Range<u64>) -> Vec<Range<u64>>;
+}
+
+impl Query for Almanac {
+    fn resolve(&self, seed: u64) -> Vec<(Category, u64)> {
+        self.path_for(seed)
+    }
+
+    fn locate(&self, location: u64) -> Vec<(Category, u64)> {
+        self.path_back(location)
+    }
+
+    fn is_planted(&self, seed: u64) -> bool {
+        Almanac::is_planted(self, seed)
+    }
+
+    fn seeds_located_in(&self, locations: Range<u64>) -> Vec<Range<u64>> {
+        self.seeds_for(locations)
+    }
+
+    fn planted_seeds_located_in(&self, locations: Range<u64>) -> Vec<Range<u64>> {
+        self.planted(&self.seeds_for(locations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+    use crate::part2::parse;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        let almanac = parse(&example.input)?;
+        let path = almanac.resolve(82);
+        assert_eq!(Some(&(Category::Seed, 82)), path.first());
+        assert_eq!(Some(&(Category::Location, 46)), path.last());
+        let back = almanac.locate(46);
+        assert_eq!(
+            path.into_iter().rev().collect::<Vec<_>>(),
+            back,
+            "resolving and locating to agree"
+        );
+        assert!(Query::is_planted(&almanac, 82));
+        assert!(!Query::is_planted(&almanac, 78));
+
+        assert_eq!(vec![82..83], almanac.planted_seeds_located_in(0..47));
+        assert!(almanac.planted_seeds_located_in(0..46).is_empty());
+        let seeds = almanac.seeds_located_in(0..47);
+        assert!(seeds.iter().any(|seeds| seeds.contains(&82)));
+        for seed in seeds.iter().flat_map(|seeds| seeds.clone()) {
+            let (_, location) = *almanac.resolve(seed).last().unwrap();
+            assert!(location < 47, "seed {seed} to reach a location below 47");
+        }
+        Ok(())
+    }
+}
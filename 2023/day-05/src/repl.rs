@@ -0,0 +1,147 @@
+use std::{fmt::Display, ops::Range};
+
+use crate::{custom_error::AocError, part1, part2, query::Query, validate::validate};
+
+/// Every query [`Session::query`] answers, with its arguments and what it shows.
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("seeds", "every seed with the location it's planted at"),
+    ("resolve <seed>", "every number a seed maps to, down to its location"),
+    (
+        "locate <location>",
+        "every number a location maps back to, up to its seed, and whether that seed is in a seed range",
+    ),
+    (
+        "reach <start> <end>",
+        "every seed that ends up at a location from start up to but not including end, and which of them are planted",
+    ),
+    ("check", "every warning about the maps, like ranges that overlap"),
+];
+
+/// The almanac, read both ways the parts read it.
+pub struct Session {
+    seeds: part1::Almanac,
+    seed_ranges: part2::Almanac,
+    /// The warnings about the maps, already rendered, errors fail to parse.
+    warnings: Vec<String>,
+}
+
+impl Session {
+    pub fn parse(input: &str) -> miette::Result<Self, AocError> {
+        Ok(Self {
+            seeds: part1::parse(input)?,
+            seed_ranges: part2::parse(input)?,
+            warnings: validate(input)
+                .into_iter()
+                .map(|warning| format!("{:?}", miette::Report::new(warning)))
+                .collect(),
+        })
+    }
+
+    pub fn query(&self, query: &str) -> miette::Result<String, AocError> {
+        let invalid = |reason: &str| AocError::InvalidQuery {
+            query: query.to_string(),
+            reason: reason.to_string(),
+        };
+        let number = |arg: &str| {
+            arg.parse::<u64>()
+                .map_err(|_| invalid("needs a number that fits in 64 bits"))
+        };
+        match query.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["seeds"] => Ok(self
+                .seeds
+                .seeds()
+                .iter()
+                .map(|&seed| path(&self.seed_ranges.resolve(seed), " → "))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ["resolve", seed] => Ok(path(&self.seed_ranges.resolve(number(seed)?), " → ")),
+            ["locate", location] => {
+                let back = self.seed_ranges.locate(number(location)?);
+                let (_, seed) = back.last().expect("the path to start at the location");
+                let planted = if self.seed_ranges.is_planted(*seed) {
+                    "in a seed range"
+                } else {
+                    "in no seed range"
+                };
+                Ok(format!("{} ({planted})", path(&back, " ← ")))
+            }
+            ["reach", start, end] => {
+                let locations = number(start)?..number(end)?;
+                let seeds = self.seed_ranges.seeds_located_in(locations.clone());
+                let planted = self.seed_ranges.planted_seeds_located_in(locations.clone());
+                Ok(format!(
+                    "{} seeds reach locations {locations:?}: {}\n{} of them are planted: {}",
+                    count(&seeds),
+                    ranges(&seeds),
+                    count(&planted),
+                    ranges(&planted)
+                ))
+            }
+            ["check"] if self.warnings.is_empty() => Ok("the maps look fine".to_string()),
+            ["check"] => Ok(self.warnings.join("\n")),
+            ["seeds" | "resolve" | "locate" | "reach" | "check", ..] => {
+                Err(invalid("has the wrong arguments"))
+            }
+            _ => Err(invalid("isn't a query")),
+        }
+    }
+}
+
+fn path(path: &[(impl Display, u64)], arrow: &str) -> String {
+    path.iter()
+        .map(|(category, num)| format!("{category} {num}"))
+        .collect::<Vec<_>>()
+        .join(arrow)
+}
+
+fn count(ranges: &[Range<u64>]) -> u64 {
+    ranges.iter().map(|range| range.end - range.start).sum()
+}
+
+fn ranges(ranges: &[Range<u64>]) -> String {
+    ranges
+        .iter()
+        .map(|range| format!("{range:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+
+    #[test]
+    fn test_query() -> miette::Result<()> {
+        let example = Example::load(Path::new("examples/part1-example.txt"));
+        let session = Session::parse(&example.input)?;
+        assert_eq!(
+            "seed 79 → soil 81 → fertilizer 81 → water 81 → light 74 → temperature 78 → humidity 78 → location 82",
+            session.query("resolve 79")?
+        );
+        assert_eq!(4, session.query("seeds")?.lines().count());
+        assert_eq!(
+            "location 46 ← humidity 46 ← temperature 45 ← light 77 ← water 84 ← fertilizer 84 ← soil 84 ← seed 82 (in a seed range)",
+            session.query("locate 46")?
+        );
+        assert_eq!(
+            "2 seeds reach locations 46..48: 82..84\n2 of them are planted: 82..84",
+            session.query("reach 46 48")?
+        );
+        assert_eq!("the maps look fine", session.query("check")?);
+        let session = Session::parse(&example.input.replace("0 15 37", "0 15 0"))?;
+        assert!(session
+            .query("check")?
+            .contains("a range of the soil-to-fertilizer map is empty"));
+        for query in ["resolve", "resolve -1", "locate 1 2", "gear 1"] {
+            assert!(matches!(
+                session.query(query),
+                Err(AocError::InvalidQuery { .. })
+            ));
+        }
+        Ok(())
+    }
+}
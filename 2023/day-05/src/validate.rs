@@ -0,0 +1,200 @@
+use std::ops::Range;
+
+use miette::{Diagnostic, Severity, SourceSpan};
+
+use crate::custom_error::AocError;
+
+/// A `destination source length` line of one of the maps.
+struct Line {
+    destination: Range<u64>,
+    source: Range<u64>,
+    span: SourceSpan,
+}
+
+/// Every problem with the almanac's maps, errors and warnings alike, in the order
+/// of the lines they're on. Lines that aren't ranges are left to the parser.
+pub fn validate(input: &str) -> Vec<AocError> {
+    let mut problems = vec![];
+    let mut map: Option<(&str, Vec<Line>)> = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let span = SourceSpan::from((offset, line.trim_end().len()));
+        offset += line.len();
+        if let Some(name) = line.trim_end().strip_suffix(" map:") {
+            if let Some((name, lines)) = map.replace((name, vec![])) {
+                problems.extend(overlaps(input, name, &lines));
+            }
+            continue;
+        }
+        let Some((name, lines)) = &mut map else {
+            continue;
+        };
+        let numbers = line
+            .split_whitespace()
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>();
+        let Ok(&[destination, source, length]) = numbers.as_deref() else {
+            continue;
+        };
+
+        if length == 0 {
+            problems.push(AocError::EmptyMapRange {
+                src: input.to_string(),
+                span,
+                map: name.to_string(),
+            });
+            continue;
+        }
+        let (Some(destination_end), Some(source_end)) =
+            (destination.checked_add(length), source.checked_add(length))
+        else {
+            problems.push(AocError::Overflow {
+                operation: format!("a range of the {name} map"),
+                src: input.to_string(),
+                span,
+            });
+            continue;
+        };
+        lines.push(Line {
+            destination: destination..destination_end,
+            source: source..source_end,
+            span,
+        });
+    }
+    if let Some((name, lines)) = map {
+        problems.extend(overlaps(input, name, &lines));
+    }
+
+    problems.sort_by_key(|problem| {
+        problem
+            .labels()
+            .and_then(|mut labels| labels.next())
+            .map(|label| label.offset())
+    });
+    problems
+}
+
+/// Fails with the first of the problems [`validate`] finds that isn't just a warning.
+pub(crate) fn check(input: &str) -> Result<(), AocError> {
+    match validate(input)
+        .into_iter()
+        .find(|problem| problem.severity() != Some(Severity::Warning))
+    {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Finds every line of a map whose source or destination range overlaps one of
+/// the lines before it. Sorting the ranges first keeps this fast on large inputs.
+fn overlaps(input: &str, map: &str, lines: &[Line]) -> Vec<AocError> {
+    let mut problems = vec![];
+    for sources in [true, false] {
+        let range = |line: &Line| {
+            if sources {
+                line.source.clone()
+            } else {
+                line.destination.clone()
+            }
+        };
+        let mut sorted = lines.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|line| (range(line).start, line.span.offset()));
+
+        let mut furthest: Option<&Line> = None;
+        for line in sorted {
+            match furthest {
+                Some(furthest_line) if range(line).start < range(furthest_line).end => {
+                    // point at whichever of the two lines comes later
+                    let (span, other) = if line.span.offset() < furthest_line.span.offset() {
+                        (furthest_line.span, line.span)
+                    } else {
+                        (line.span, furthest_line.span)
+                    };
+                    let src = input.to_string();
+                    let map = map.to_string();
+                    problems.push(if sources {
+                        AocError::OverlappingSources {
+                            src,
+                            span,
+                            other,
+                            map,
+                        }
+                    } else {
+                        AocError::OverlappingDestinations {
+                            src,
+                            span,
+                            other,
+                            map,
+                        }
+                    });
+                    if range(line).end > range(furthest_line).end {
+                        furthest = Some(line);
+                    }
+                }
+                _ => furthest = Some(line),
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_common::examples::Example;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let example = Example::load(Path::new("examples/part2-example.txt"));
+        assert!(validate(&example.input).is_empty());
+
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n60 99 5\n10 0 0\n\nsoil-to-fertilizer map:\n0 15 37\n18446744073709551615 0 2\n0 52 2\n";
+        let problems = validate(input);
+        let line = |n: usize| {
+            input
+                .split_inclusive('\n')
+                .take(n - 1)
+                .map(str::len)
+                .sum::<usize>()
+        };
+        let found = problems
+            .iter()
+            .map(|problem| {
+                let kind = match problem {
+                    AocError::OverlappingSources { .. } => "sources",
+                    AocError::OverlappingDestinations { .. } => "destinations",
+                    AocError::EmptyMapRange { .. } => "empty",
+                    AocError::Overflow { .. } => "overflow",
+                    _ => "other",
+                };
+                let span = problem
+                    .labels()
+                    .and_then(|mut labels| labels.next())
+                    .expect("every problem to point at its line");
+                (kind, span.offset())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("sources", line(6)),
+                ("destinations", line(6)),
+                ("empty", line(7)),
+                ("overflow", line(11)),
+                ("destinations", line(12)),
+            ],
+            found
+        );
+        assert!(matches!(
+            check(input),
+            Err(AocError::OverlappingSources { .. })
+        ));
+        assert_eq!(
+            Some(Severity::Warning),
+            problems[1].severity(),
+            "overlapping destinations to only be a warning"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use day_01::{matcher::NumberMatcher, part1, part2};
+use libfuzzer_sys::fuzz_target;
+
+// the same bytes are tried as puzzle input and as a vocabulary file
+fuzz_target!(|input: &str| {
+    let _ = part1::process(input);
+    let _ = part2::process(input);
+    let _ = NumberMatcher::from_config(input);
+});
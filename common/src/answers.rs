@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use miette::{miette, Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+/// An answer that is known to be right for one particular input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub answer: String,
+    pub input_sha256: String,
+}
+
+/// Whether a solution gave the recorded answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// Nothing is recorded for this part, or it was recorded for another input.
+    Unknown,
+}
+
+/// The answers registry every year shares, read from `answers.txt`.
+///
+/// Every non-empty line that doesn't start with `#` is `year day part answer input_sha256`,
+/// so an answer is only checked against the input it was recorded for.
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u16, u8, u8), Answer>,
+}
+
+impl Answers {
+    /// Where the repository keeps its recorded answers, next to the year workspaces.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("answers.txt")
+    }
+
+    /// Reads the registry at `path`, a missing file just has no answers.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::parse(&contents).wrap_err_with(|| format!("read {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", path.display())),
+        }
+    }
+
+    pub fn parse(contents: &str) -> miette::Result<Self> {
+        let mut answers = BTreeMap::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                miette!(
+                    "line {} isn't `year day part answer input_sha256`",
+                    index + 1
+                )
+            };
+            let [year, day, part, answer, input_sha256] = line
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid())?;
+            let year = year.parse().map_err(|_| invalid())?;
+            let day = day.parse().map_err(|_| invalid())?;
+            let part = part.parse().map_err(|_| invalid())?;
+            answers.insert(
+                (year, day, part),
+                Answer {
+                    answer: answer.to_string(),
+                    input_sha256: input_sha256.to_string(),
+                },
+            );
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    /// The answer recorded for this very input.
+    pub fn expected(&self, year: u16, day: u8, part: u8, input_sha256: &str) -> Option<&str> {
+        self.get(year, day, part)
+            .filter(|known| known.input_sha256 == input_sha256)
+            .map(|known| known.answer.as_str())
+    }
+
+    /// Checks `answer` against what was recorded for the same input.
+    pub fn verdict(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        input_sha256: &str,
+        answer: &str,
+    ) -> Verdict {
+        match self.expected(year, day, part, input_sha256) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict() -> miette::Result<()> {
+        let answers = Answers::parse(
+            "# year day part answer input_sha256
+2023 4 2 30 abc
+",
+        )?;
+        assert_eq!(Verdict::Pass, answers.verdict(2023, 4, 2, "abc", "30"));
+        assert_eq!(Verdict::Fail, answers.verdict(2023, 4, 2, "abc", "31"));
+        assert_eq!(Verdict::Unknown, answers.verdict(2023, 4, 2, "def", "30"));
+        assert_eq!(Verdict::Unknown, answers.verdict(2023, 4, 1, "abc", "30"));
+        assert_eq!(Verdict::Unknown, answers.verdict(2022, 4, 2, "abc", "30"));
+        assert!(Answers::parse("4 2 30 abc").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use miette::{Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+use crate::{answers::Answers, days::Solution, input::InputMode, run, year::Year};
+
+/// How long one part took over a number of runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bench {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub runs: usize,
+    /// The median of parsing and solving together, in milliseconds.
+    pub median_ms: f64,
+}
+
+/// Solves `solution` of `year` from its own input `runs` times.
+pub fn bench(
+    year: &Year,
+    solution: &Solution,
+    runs: usize,
+    answers: &Answers,
+) -> miette::Result<Bench> {
+    let path = year.input_path(solution.day, solution.part);
+    let mut times = (0..runs.max(1))
+        .map(|_| {
+            let run = run::run_file(year.year, solution, &path, InputMode::Auto, answers)?;
+            Ok(run.parse_ms.unwrap_or_default() + run.solve_ms)
+        })
+        .collect::<miette::Result<Vec<_>>>()?;
+    times.sort_by(f64::total_cmp);
+
+    let middle = times.len() / 2;
+    let median_ms = if times.len() % 2 == 0 {
+        (times[middle - 1] + times[middle]) / 2.0
+    } else {
+        times[middle]
+    };
+    Ok(Bench {
+        year: year.year,
+        day: solution.day,
+        part: solution.part,
+        runs: times.len(),
+        median_ms,
+    })
+}
+
+/// The benchmark results of every year, the README is generated from them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Benches {
+    pub benches: Vec<Bench>,
+}
+
+impl Benches {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("benchmarks.json")
+    }
+
+    /// Reads the results at `path`, a missing file just has no results.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Self {
+                benches: serde_json::from_str(&contents)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("read {}", path.display()))?,
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let json = serde_json::to_string_pretty(&self.benches).into_diagnostic()?;
+        fs::write(path, json + "\n")
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write {}", path.display()))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Bench> {
+        self.benches
+            .iter()
+            .find(|bench| (bench.year, bench.day, bench.part) == (year, day, part))
+    }
+
+    /// Replaces the earlier result for the same part, keeping them ordered by year, day and part.
+    pub fn insert(&mut self, bench: Bench) {
+        let key = |b: &Bench| (b.year, b.day, b.part);
+        self.benches.retain(|b| key(b) != key(&bench));
+        self.benches.push(bench);
+        self.benches.sort_by_key(key);
+    }
+}
@@ -0,0 +1,27 @@
+use std::{path::PathBuf, time::Duration};
+
+use aoc_common::mock::MockServer;
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(
+    about = "Serves inputs and takes answers like the Advent of Code site, for working offline"
+)]
+struct Args {
+    /// Holds `<year>/day-<dd>.txt` inputs, `<year>/leaderboard-<id>.json` leaderboards and the
+    /// right answers in an `answers.txt` registry
+    dir: PathBuf,
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+    /// How many seconds answers are refused after a wrong one
+    #[arg(long, default_value_t = 60)]
+    cooldown: u64,
+}
+
+fn main() -> miette::Result<()> {
+    let args = Args::parse();
+    let server = MockServer::start(&args.addr, args.dir, Duration::from_secs(args.cooldown))?;
+    eprintln!("serving on {}", server.base_url());
+    server.join();
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use sha2::{Digest, Sha256};
+
+/// Everything the build script of a day crate does, which is all it calls.
+pub fn day() {
+    // the example tests are generated from the files in here, so adding one
+    // has to rebuild the tests
+    println!("cargo:rerun-if-changed=examples");
+    emit_source_hash();
+}
+
+/// Sets `SOURCE_HASH` for the day crate whose build script calls this, so the
+/// runner only reuses a cached answer while the sources that gave it stay the same.
+///
+/// The hash covers the crate's `src` and `Cargo.toml`, the same files of this
+/// crate, which every day builds on, the `Cargo.lock` of the workspace, and how
+/// the crate is built: its features, the profile and the compiler's version.
+pub fn emit_source_hash() {
+    let manifest_dir =
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("cargo to set CARGO_MANIFEST_DIR"));
+    let roots = [
+        manifest_dir.as_path(),
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+    ];
+    for root in roots {
+        println!("cargo:rerun-if-changed={}", root.join("src").display());
+        println!(
+            "cargo:rerun-if-changed={}",
+            root.join("Cargo.toml").display()
+        );
+    }
+    let lock_file = manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|lock_file| lock_file.is_file());
+    if let Some(lock_file) = &lock_file {
+        println!("cargo:rerun-if-changed={}", lock_file.display());
+    }
+
+    let mut settings = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|f| format!("feature={f}"))
+        })
+        .collect::<Vec<_>>();
+    for key in ["PROFILE", "OPT_LEVEL"] {
+        settings.push(format!("{key}={}", env::var(key).unwrap_or_default()));
+    }
+    settings.push(format!("rustc={}", rustc_version()));
+    println!(
+        "cargo:rustc-env=SOURCE_HASH={}",
+        source_hash(&roots, lock_file.as_deref(), &settings)
+    );
+}
+
+/// `rustc -vV` of the compiler cargo builds with, which includes its commit.
+fn rustc_version() -> String {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc)
+        .arg("-vV")
+        .output()
+        .expect("the compiler to tell its version");
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Hashes the sources under each of `roots`, the `lock_file` if there is one
+/// and the build `settings`, in an order that doesn't depend on the file
+/// system or `env`.
+pub fn source_hash(roots: &[&Path], lock_file: Option<&Path>, settings: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for (index, root) in roots.iter().enumerate() {
+        for file in ["src", "Cargo.toml"] {
+            hash_files(root, Path::new(file), index, &mut hasher);
+        }
+    }
+    if let Some(lock_file) = lock_file {
+        update(&mut hasher, b"Cargo.lock");
+        update(
+            &mut hasher,
+            &fs::read(lock_file).expect("the lock file to be readable"),
+        );
+    }
+    let mut settings = settings.to_vec();
+    settings.sort();
+    for setting in settings {
+        update(&mut hasher, setting.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn hash_files(root: &Path, path: &Path, index: usize, hasher: &mut Sha256) {
+    let full = root.join(path);
+    if full.is_dir() {
+        let mut entries = fs::read_dir(&full)
+            .expect("the crate's sources to be readable")
+            .map(|entry| {
+                entry
+                    .expect("the crate's sources to be readable")
+                    .file_name()
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        for entry in entries {
+            hash_files(root, &path.join(entry), index, hasher);
+        }
+    } else {
+        // paths relative to their root keep the hash the same in every checkout
+        update(hasher, format!("{index}:{}", path.display()).as_bytes());
+        update(
+            hasher,
+            &fs::read(&full).expect("the crate's sources to be readable"),
+        );
+    }
+}
+
+/// Feeds the length first, so two files can't run together into the same bytes.
+fn update(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_hash() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let hash = source_hash(&[root], None, &[]);
+        assert_eq!(64, hash.len());
+        assert_eq!(
+            hash,
+            source_hash(&[root], None, &[]),
+            "the hash to be stable"
+        );
+
+        let settings = ["feature=U128".to_string(), "PROFILE=release".to_string()];
+        let with_settings = source_hash(&[root], None, &settings);
+        assert_ne!(hash, with_settings);
+        assert_eq!(
+            with_settings,
+            source_hash(&[root], None, &[settings[1].clone(), settings[0].clone()]),
+            "the order settings are listed in not to matter"
+        );
+        assert_ne!(
+            with_settings,
+            source_hash(
+                &[root],
+                None,
+                &["PROFILE=debug".to_string(), settings[0].clone()]
+            )
+        );
+        assert_ne!(hash, source_hash(&[root, root], None, &[]));
+        assert_ne!(
+            hash,
+            source_hash(&[root], Some(&root.join("Cargo.lock")), &[])
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use miette::{Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+/// What a cached answer is only valid for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input_sha256: &'a str,
+    /// The day crate's `SOURCE_HASH`.
+    pub source_hash: &'a str,
+}
+
+/// An answer and how long it took, as the part was last solved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub answer: String,
+    pub parse_ms: Option<f64>,
+    pub solve_ms: f64,
+}
+
+/// Answers `aoc run` already found, one file per key.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Where the repository keeps the cache, next to the year workspaces.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(".aoc-cache")
+    }
+
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, key: &Key) -> PathBuf {
+        self.dir.join(key.year.to_string()).join(format!(
+            "day-{:02}-part{}-{}-{}.json",
+            key.day, key.part, key.input_sha256, key.source_hash
+        ))
+    }
+
+    /// The cached entry, an unreadable one just isn't cached.
+    pub fn get(&self, key: &Key) -> Option<Entry> {
+        let contents = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn insert(&self, key: &Key, entry: &Entry) -> miette::Result<()> {
+        let path = self.path(key);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("create {}", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(entry).into_diagnostic()?;
+        fs::write(&path, json + "\n")
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write {}", path.display()))
+    }
+
+    /// Forgets every cached answer.
+    pub fn clear(&self) -> miette::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("remove {}", self.dir.display())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() -> miette::Result<()> {
+        let cache = Cache::new(std::env::temp_dir().join("aoc-test-cache"));
+        cache.clear()?;
+        let key = Key {
+            year: 2023,
+            day: 5,
+            part: 2,
+            input_sha256: "abc",
+            source_hash: "def",
+        };
+        let entry = Entry {
+            answer: "46".to_string(),
+            parse_ms: Some(0.5),
+            solve_ms: 1.5,
+        };
+        assert_eq!(None, cache.get(&key));
+        cache.insert(&key, &entry)?;
+        assert_eq!(Some(entry), cache.get(&key));
+        assert_eq!(
+            None,
+            cache.get(&Key {
+                source_hash: "ghi",
+                ..key
+            })
+        );
+
+        cache.clear()?;
+        assert_eq!(None, cache.get(&key));
+        cache.clear()?;
+        Ok(())
+    }
+}
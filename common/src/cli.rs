@@ -0,0 +1,645 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use clap::{Args, Parser, Subcommand};
+use miette::{miette, Context, IntoDiagnostic};
+
+use crate::{
+    answers::Answers,
+    bench::{self, Benches},
+    cache::Cache,
+    client::{self, Client},
+    days::Solution,
+    examples,
+    generate::Knobs,
+    input::InputMode,
+    panics::Panicked,
+    parallel, readme, repl,
+    run::{self, Format, Run},
+    submissions::Submissions,
+    verify::{self, Outcome},
+    watch,
+    year::Year,
+};
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one part of a day, or every solved part with --all
+    Run {
+        /// With --all, only solve this year's parts
+        #[arg(required_unless_present = "all")]
+        year: Option<u16>,
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        part: Option<u8>,
+        /// Solve every part that is solved, printing a line for each
+        #[arg(long)]
+        all: bool,
+        /// Read the puzzle input from this file instead of the day's own input
+        #[arg(long, conflicts_with = "all")]
+        input: Option<PathBuf>,
+        /// How to feed the input to the solution
+        #[arg(long, value_enum, default_value_t)]
+        mode: InputMode,
+        /// Print just the answer, or a JSON object with timings and the verdict
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// Solve again even when neither the input nor the day's sources changed
+        #[arg(long)]
+        no_cache: bool,
+        /// With --all, how many parts to solve at once; always one with dhat-heap
+        // clap drops `requires = "all"` whenever the day is given, as --all conflicts
+        // with it, so conflicting with the day is what rejects these without --all
+        #[arg(long, conflicts_with = "day", default_value_t = 1)]
+        jobs: usize,
+        /// With --all, how many seconds a part gets before it's reported as timed out
+        #[arg(long, conflicts_with = "day", default_value_t = 60)]
+        timeout: u64,
+    },
+    /// Manage the answers `aoc run` keeps from earlier runs
+    Cache {
+        #[command(subcommand)]
+        cache: CacheCommand,
+    },
+    /// Check every solved part, or just one year's or day's, against the recorded answers
+    Verify {
+        year: Option<u16>,
+        day: Option<u8>,
+        /// Also write the results to this file as a JUnit XML report
+        #[arg(long)]
+        junit: Option<PathBuf>,
+    },
+    /// Time every solved part, or just one year's or day's, and record the results
+    Bench {
+        year: Option<u16>,
+        day: Option<u8>,
+        /// How many times to solve each part
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+    },
+    /// Download a day's input into its crate
+    Fetch {
+        year: u16,
+        day: u8,
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Submit an answer, solving the part for it when none is given
+    ///
+    /// Answers the site already rejected, or that can't be right given what it said
+    /// about earlier ones, are refused without asking it.
+    Submit {
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+        /// Submit even if earlier submissions rule the answer out
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Show a private leaderboard
+    Leaderboard {
+        year: u16,
+        /// The id of the leaderboard's owner
+        id: u64,
+        #[command(flatten)]
+        site: Site,
+    },
+    /// Write the examples from a saved puzzle page into the day's example tests
+    Examples {
+        year: u16,
+        day: u8,
+        /// The puzzle's page, as the browser saved it
+        page: PathBuf,
+        /// Replace examples that were already written
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check a part every time its day's sources, examples or input change
+    ///
+    /// Each round rebuilds the day, runs its example tests and solves the input,
+    /// showing how the answer compares to the round before.
+    Watch {
+        year: u16,
+        day: u8,
+        part: u8,
+        /// How often to look for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Generate documents from what has been solved so far
+    Report {
+        #[command(subcommand)]
+        report: Report,
+    },
+    /// Parse a day's input once and answer queries about it, `help` lists them
+    Repl {
+        year: u16,
+        day: u8,
+        /// Parse this file instead of the day's own input
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Generate a random puzzle input for a day
+    Gen {
+        year: u16,
+        day: u8,
+        /// The same seed always generates the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// How big the input is, see each day's generator for what it means
+        #[arg(long)]
+        size: Option<usize>,
+        /// How crowded 2023's day 3 grid is, from 0 to 1
+        #[arg(long, value_parser = fraction)]
+        density: Option<f64>,
+        /// Write the input to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Forget every cached answer
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum Report {
+    /// Update every year's table of days in the README with the latest benchmark results
+    Readme {
+        /// Update this file instead of the repository's README
+        #[arg(long)]
+        readme: Option<PathBuf>,
+    },
+}
+
+/// Where the site is and who to be on it.
+#[derive(Args)]
+struct Site {
+    /// The site, or a local stand-in like `aoc-mock`
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::BASE_URL)]
+    base_url: String,
+    /// The value of the site's `session` cookie
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: String,
+}
+
+impl Site {
+    fn client(&self) -> Client {
+        Client::new(&self.base_url, &self.session)
+    }
+}
+
+/// Runs the command line against the years a workspace's `aoc` binary was built with.
+pub fn run(years: &[Year]) -> miette::Result<()> {
+    match Cli::parse().command {
+        Command::Run {
+            year,
+            day,
+            part,
+            all,
+            input,
+            mode,
+            format,
+            no_cache,
+            jobs,
+            timeout,
+        } => {
+            let answers = Arc::new(Answers::load(&Answers::default_path())?);
+            let cache = (!no_cache).then(|| Cache::new(Cache::default_path()));
+
+            if all {
+                let parts = select(years, year)?
+                    .into_iter()
+                    .flat_map(|year| year.solutions.iter().map(move |solution| (*year, solution)))
+                    .collect::<Vec<_>>();
+                let tasks = parts
+                    .iter()
+                    .map(|&(year, solution)| {
+                        let (answers, cache) = (answers.clone(), cache.clone());
+                        Box::new(move || {
+                            let path = year.input_path(solution.day, solution.part);
+                            solve(&year, solution, &path, mode, &answers, cache.as_ref())
+                        }) as parallel::Task<Run>
+                    })
+                    .collect();
+
+                let (mut failed, mut timed_out, mut panicked) = (0, 0, 0);
+                let timeout = Duration::from_secs(timeout);
+                // dhat's counters are shared by the whole process, so parts solved
+                // side by side would count each other's allocations
+                let jobs = if cfg!(feature = "dhat-heap") { 1 } else { jobs };
+                parallel::run(tasks, jobs, timeout, |index, status| {
+                    let (year, solution) = parts[index];
+                    let part = format!("{} day {} part {}", year.year, solution.day, solution.part);
+                    let (status, error) = match status {
+                        parallel::Status::Done(Ok(run)) => {
+                            match format {
+                                Format::Text => println!("{run}"),
+                                Format::Json => println!("{}", run.to_json()),
+                            }
+                            return;
+                        }
+                        parallel::Status::Done(Err(report)) => {
+                            let status = if report.downcast_ref::<Panicked>().is_some() {
+                                panicked += 1;
+                                "panicked"
+                            } else {
+                                failed += 1;
+                                "failed"
+                            };
+                            eprintln!("{report:?}");
+                            (status, chain(&report))
+                        }
+                        // only panics outside of the solution itself get this far
+                        parallel::Status::Panicked(panic) => {
+                            panicked += 1;
+                            let path = year.input_path(solution.day, solution.part);
+                            let report = miette::Report::new(Panicked::new(
+                                path.display().to_string(),
+                                panic,
+                            ))
+                            .wrap_err(format!("process {part}"));
+                            eprintln!("{report:?}");
+                            ("panicked", chain(&report))
+                        }
+                        parallel::Status::TimedOut(timeout) => {
+                            timed_out += 1;
+                            eprintln!("{part} timed out after {} s", timeout.as_secs());
+                            (
+                                "timed_out",
+                                format!("still running after {} s", timeout.as_secs()),
+                            )
+                        }
+                    };
+                    if format == Format::Json {
+                        println!(
+                            "{}",
+                            serde_json::json!({
+                                "year": year.year,
+                                "day": solution.day,
+                                "part": solution.part,
+                                "status": status,
+                                "error": error,
+                            })
+                        );
+                    }
+                });
+                let unfinished = failed + panicked + timed_out;
+                if unfinished > 0 {
+                    let total = parts.len();
+                    return Err(miette!(
+                        "{unfinished} of {total} parts didn't finish: \
+                         {failed} failed, {panicked} panicked, {timed_out} timed out"
+                    ));
+                }
+            } else {
+                let (Some(year), Some(day), Some(part)) = (year, day, part) else {
+                    unreachable!("clap to require them without --all")
+                };
+                let year = find(years, year)?;
+                let solution = year.find(day, part).ok_or_else(|| {
+                    miette!("{} day {day} part {part} isn't solved yet", year.year)
+                })?;
+                let path = input.unwrap_or_else(|| year.input_path(day, part));
+                let run = solve(year, solution, &path, mode, &answers, cache.as_ref())?;
+                match format {
+                    Format::Text => println!("{}", run.answer),
+                    Format::Json => println!("{}", run.to_json()),
+                }
+            }
+        }
+        Command::Cache {
+            cache: CacheCommand::Clear,
+        } => Cache::new(Cache::default_path()).clear()?,
+        Command::Verify { year, day, junit } => {
+            let answers = Answers::load(&Answers::default_path())?;
+            let mut cases = Vec::new();
+            for year in select(years, year)? {
+                let solutions = year
+                    .solutions
+                    .iter()
+                    .filter(|solution| day.is_none_or(|day| solution.day == day))
+                    .copied()
+                    .collect::<Vec<_>>();
+                cases.extend(verify::verify(year, &solutions, &answers));
+            }
+            for case in &cases {
+                println!("{case}");
+                if let Outcome::Error { diagnostic, .. } = &case.outcome {
+                    eprintln!("{diagnostic}");
+                }
+            }
+            if let Some(path) = junit {
+                std::fs::write(&path, verify::junit(&cases))
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("write {}", path.display()))?;
+            }
+
+            let broken = cases
+                .iter()
+                .filter(|case| matches!(case.outcome, Outcome::Fail(_) | Outcome::Error { .. }))
+                .count();
+            if broken > 0 {
+                return Err(miette!("{broken} of {} parts are wrong", cases.len()));
+            }
+        }
+        Command::Bench { year, day, runs } => {
+            let answers = Answers::load(&Answers::default_path())?;
+            let path = Benches::default_path();
+            let mut benches = Benches::load(&path)?;
+            for year in select(years, year)? {
+                for solution in year
+                    .solutions
+                    .iter()
+                    .filter(|solution| day.is_none_or(|day| solution.day == day))
+                {
+                    let result =
+                        bench::bench(year, solution, runs, &answers).wrap_err_with(|| {
+                            format!(
+                                "bench {} day {} part {}",
+                                year.year, solution.day, solution.part
+                            )
+                        })?;
+                    println!(
+                        "{} day {} part {}: {:.3} ms",
+                        result.year, result.day, result.part, result.median_ms
+                    );
+                    benches.insert(result);
+                }
+            }
+            benches.save(&path)?;
+        }
+        Command::Fetch { year, day, site } => {
+            let year = find(years, year)?;
+            let input = site.client().input(year.year, day)?;
+            // every part reads its own copy of the same input
+            for part in [1, 2] {
+                let path = year.input_path(day, part);
+                std::fs::write(&path, &input)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("write {}", path.display()))?;
+            }
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            force,
+            site,
+        } => {
+            let year = find(years, year)?;
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let solution = year.find(day, part).ok_or_else(|| {
+                        miette!("{} day {day} part {part} isn't solved yet", year.year)
+                    })?;
+                    let answers = Answers::load(&Answers::default_path())?;
+                    let path = year.input_path(day, part);
+                    run::run_file(year.year, solution, &path, InputMode::Auto, &answers)?.answer
+                }
+            };
+            let client = site.client();
+            let path = Submissions::default_path();
+            let mut submissions = Submissions::load(&path)?;
+            if !force {
+                submissions.check(client.base_url(), year.year, day, part, &answer)?;
+            }
+            let feedback = client.submit(year.year, day, part, &answer)?;
+            println!("{answer}: {feedback}");
+            submissions.record(client.base_url(), year.year, day, part, &answer, feedback);
+            submissions.save(&path)?;
+        }
+        Command::Leaderboard { year, id, site } => {
+            let leaderboard = site.client().leaderboard(year, id)?;
+            let mut members = leaderboard.members.values().collect::<Vec<_>>();
+            members.sort_by_key(|member| std::cmp::Reverse(member.local_score));
+            for member in members {
+                let name = member
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("(anonymous user #{})", member.id));
+                println!("{:>5} {:>3}⭐ {name}", member.local_score, member.stars);
+            }
+        }
+        Command::Examples {
+            year,
+            day,
+            page,
+            force,
+        } => {
+            let year = find(years, year)?;
+            let html = std::fs::read_to_string(&page)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", page.display()))?;
+            let examples = examples::extract(&html)
+                .wrap_err_with(|| format!("find the examples in {}", page.display()))?;
+            let dir = year.day_dir(day).join("examples");
+            if !dir.is_dir() {
+                return Err(miette!(
+                    "{} doesn't exist, create day {day} first",
+                    dir.display()
+                ));
+            }
+            for example in examples {
+                let path = dir.join(format!("part{}-example.txt", example.part));
+                let fixture = example.fixture();
+                let old = std::fs::read_to_string(&path).ok();
+                if old.as_deref() == Some(fixture.as_str()) {
+                    continue;
+                }
+                // the template's examples have no answer yet
+                let written = old.is_some_and(|old| !old.starts_with("answer: \n"));
+                if written && !force {
+                    return Err(miette!(
+                        help = "replace it with --force",
+                        "{} already has another example",
+                        path.display()
+                    ));
+                }
+                std::fs::write(&path, fixture)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("write {}", path.display()))?;
+                println!("wrote {}", path.display());
+            }
+        }
+        Command::Watch {
+            year,
+            day,
+            part,
+            interval,
+        } => {
+            let year = find(years, year)?;
+            watch::watch(year, day, part, Duration::from_millis(interval))?;
+        }
+        Command::Report {
+            report: Report::Readme { readme: path },
+        } => {
+            let path = path.unwrap_or_else(readme::default_path);
+            let mut contents = std::fs::read_to_string(&path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", path.display()))?;
+            let benches = Benches::load(&Benches::default_path())?;
+            for year in years {
+                contents = readme::update(&contents, year.year, &readme::table(year, &benches))?;
+            }
+            std::fs::write(&path, contents)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("write {}", path.display()))?;
+        }
+        Command::Repl { year, day, input } => {
+            let year = find(years, year)?;
+            let open = year
+                .repl
+                .ok_or_else(|| miette!("{} has no days that can be queried", year.year))?;
+            let path = input.unwrap_or_else(|| year.input_path(day, 1));
+            let input = std::fs::read_to_string(&path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", path.display()))?;
+            let session = open(day, &input)?;
+            println!("`help` lists the queries, `quit` stops");
+            repl::repl(
+                &session,
+                &format!("{} day {day}", year.year),
+                io::stdin().lock(),
+                io::stdout().lock(),
+            )?;
+        }
+        Command::Gen {
+            year,
+            day,
+            seed,
+            size,
+            density,
+            output,
+        } => {
+            let year = find(years, year)?;
+            let generate = year
+                .generate
+                .ok_or_else(|| miette!("{} has no input generators", year.year))?;
+            let writer: Box<dyn Write> = match output {
+                Some(path) => Box::new(
+                    File::create(&path)
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("create {}", path.display()))?,
+                ),
+                None => Box::new(io::stdout().lock()),
+            };
+            let mut writer = BufWriter::new(writer);
+            generate(day, seed, &Knobs { size, density }, &mut writer)?;
+            writer.flush().into_diagnostic()?;
+        }
+    }
+    Ok(())
+}
+
+/// Solves a part, from the cache when there is one.
+fn solve(
+    year: &Year,
+    solution: &Solution,
+    path: &Path,
+    mode: InputMode,
+    answers: &Answers,
+    cache: Option<&Cache>,
+) -> miette::Result<Run> {
+    match cache {
+        Some(cache) => run::run_cached(year, solution, path, mode, answers, cache),
+        None => run::run_file(year.year, solution, path, mode, answers),
+    }
+    .wrap_err_with(|| {
+        format!(
+            "process {} day {} part {}",
+            year.year, solution.day, solution.part
+        )
+    })
+}
+
+/// Every message of an error and the ones that caused it, on one line.
+fn chain(report: &miette::Report) -> String {
+    report
+        .chain()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+fn find(years: &[Year], year: u16) -> miette::Result<&Year> {
+    years
+        .iter()
+        .find(|known| known.year == year)
+        .ok_or_else(|| {
+            let known = years
+                .iter()
+                .map(|known| known.year.to_string())
+                .collect::<Vec<_>>();
+            miette!(
+                "{year} isn't part of this workspace, its runner only knows {}",
+                known.join(", ")
+            )
+        })
+}
+
+/// Every year, or just the one asked for.
+fn select(years: &[Year], year: Option<u16>) -> miette::Result<Vec<&Year>> {
+    match year {
+        Some(year) => Ok(vec![find(years, year)?]),
+        None => Ok(years.iter().collect()),
+    }
+}
+
+/// A number from 0 to 1, which rules out NaN and the infinities too.
+fn fraction(s: &str) -> Result<f64, String> {
+    let fraction = s.parse::<f64>().map_err(|e| e.to_string())?;
+    if (0.0..=1.0).contains(&fraction) {
+        Ok(fraction)
+    } else {
+        Err(format!("{s} isn't from 0 to 1"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density() {
+        let parse =
+            |density: &str| Cli::try_parse_from(["aoc", "gen", "2023", "3", "--density", density]);
+        assert!(parse("0.5").is_ok());
+        for density in ["NaN", "inf", "-0.1", "1.5", "dense"] {
+            assert!(parse(density).is_err(), "{density} to be rejected");
+        }
+    }
+
+    #[test]
+    fn test_run_options_need_all() {
+        let parse = |args: &[&str]| Cli::try_parse_from(["aoc", "run"].iter().chain(args));
+        assert!(parse(&["2023", "1", "1"]).is_ok());
+        assert!(parse(&["--all", "--jobs", "4", "--timeout", "5"]).is_ok());
+        for option in ["--jobs", "--timeout"] {
+            assert!(
+                parse(&["2023", "1", "1", option, "4"]).is_err(),
+                "{option} without --all to be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::{collections::BTreeMap, fmt::Display, io::Read};
+
+use miette::{miette, Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+
+/// The real site, the mock server speaks the same protocol under another base URL.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "feedback")]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which way.
+    Wrong,
+    /// An answer was submitted too recently, `wait` is how long the site says is left.
+    RateLimited {
+        wait: Option<String>,
+    },
+    /// The part is solved already, or isn't unlocked yet.
+    WrongLevel,
+}
+
+impl Feedback {
+    /// Reads the feedback out of the page the site answers a submission with.
+    pub fn parse(html: &str) -> miette::Result<Self> {
+        let article = html
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(html, |(article, _)| article);
+        let feedback = if article.contains("That's the right answer") {
+            Self::Correct
+        } else if article.contains("your answer is too high") {
+            Self::TooHigh
+        } else if article.contains("your answer is too low") {
+            Self::TooLow
+        } else if article.contains("That's not the right answer") {
+            Self::Wrong
+        } else if article.contains("You gave an answer too recently") {
+            let wait = article
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Self::RateLimited { wait }
+        } else if article.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            return Err(miette!("the answer page has no feedback the client knows"));
+        };
+        Ok(feedback)
+    }
+
+    /// How the submissions log writes this feedback, without how long a rate limit lasts.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+            Self::Wrong => "wrong",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::WrongLevel => "wrong_level",
+        }
+    }
+
+    /// Reads back what [`Feedback::name`] wrote.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "correct" => Self::Correct,
+            "too_high" => Self::TooHigh,
+            "too_low" => Self::TooLow,
+            "wrong" => Self::Wrong,
+            "rate_limited" => Self::RateLimited { wait: None },
+            "wrong_level" => Self::WrongLevel,
+            _ => return None,
+        })
+    }
+
+    /// Whether the site looked at the answer and said it's wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong, too high"),
+            Self::TooLow => write!(f, "wrong, too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, try again in {wait}")
+            }
+            Self::RateLimited { wait: None } => write!(f, "rate limited"),
+            Self::WrongLevel => write!(f, "already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// A private leaderboard, as its JSON API returns it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    /// By member id.
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous members have no name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+}
+
+/// Talks to the site, or anything else that speaks its protocol at `base_url`.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// `session` is the value of the site's `session` cookie.
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("aoc-common/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> miette::Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self.send(&url, self.request("GET", &url).call())?;
+        response
+            .into_string()
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {url}"))
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> miette::Result<Feedback> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let response = self.send(
+            &url,
+            self.request("POST", &url)
+                .send_form(&[("level", &level), ("answer", answer)]),
+        )?;
+        let html = response
+            .into_string()
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {url}"))?;
+        Feedback::parse(&html).wrap_err_with(|| format!("submit to {url}"))
+    }
+
+    /// Reads the private leaderboard owned by the member with `id`.
+    pub fn leaderboard(&self, year: u16, id: u64) -> miette::Result<Leaderboard> {
+        let url = format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        );
+        let response = self.send(&url, self.request("GET", &url).call())?;
+        serde_json::from_reader(response.into_reader())
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {url}"))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    /// Turns the site's error statuses into errors with whatever it said about them.
+    fn send(
+        &self,
+        url: &str,
+        result: Result<ureq::Response, ureq::Error>,
+    ) -> miette::Result<ureq::Response> {
+        match result {
+            Ok(response) => Ok(response),
+            Err(ureq::Error::Status(status, response)) => {
+                let mut body = String::new();
+                // the status says enough when the body can't be read
+                let _ = response.into_reader().take(1024).read_to_string(&mut body);
+                Err(miette!("{url} answered {status}: {}", body.trim()))
+            }
+            Err(e) => Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("request {url}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf, time::Duration};
+
+    use super::*;
+    use crate::mock::MockServer;
+
+    fn site(name: &str) -> miette::Result<PathBuf> {
+        let dir = std::env::temp_dir().join(name);
+        fs::create_dir_all(dir.join("2023")).into_diagnostic()?;
+        let write =
+            |path: &str, contents: &str| fs::write(dir.join(path), contents).into_diagnostic();
+        write("answers.txt", "2023 5 1 35 -\n2023 5 2 46 -\n")?;
+        write("2023/day-05.txt", "seeds: 79 14 55 13\n")?;
+        write(
+            "2023/leaderboard-42.json",
+            r#"{"event":"2023","owner_id":42,"members":{
+                "42":{"id":42,"name":"owner","stars":10,"local_score":20,"global_score":0},
+                "7":{"id":7,"name":null,"stars":1,"local_score":3,"global_score":0}}}"#,
+        )?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn test_client() -> miette::Result<()> {
+        let server = MockServer::start("127.0.0.1:0", site("aoc-test-client")?, Duration::ZERO)?;
+        let client = Client::new(server.base_url(), "abc");
+
+        assert_eq!("seeds: 79 14 55 13\n", client.input(2023, 5)?);
+        assert!(client.input(2023, 6).is_err());
+        assert!(Client::new(server.base_url(), "").input(2023, 5).is_err());
+        assert!(Client::new(&format!("{}/", server.base_url()), "abc")
+            .input(2023, 5)
+            .is_ok());
+
+        assert_eq!(Feedback::WrongLevel, client.submit(2023, 5, 2, "46")?);
+        assert_eq!(Feedback::TooHigh, client.submit(2023, 5, 1, "40")?);
+        assert_eq!(Feedback::TooLow, client.submit(2023, 5, 1, "30")?);
+        assert_eq!(Feedback::Wrong, client.submit(2023, 5, 1, "thirty five")?);
+        assert_eq!(Feedback::Correct, client.submit(2023, 5, 1, "35")?);
+        assert_eq!(Feedback::WrongLevel, client.submit(2023, 5, 1, "35")?);
+        assert_eq!(Feedback::Correct, client.submit(2023, 5, 2, "46")?);
+
+        let leaderboard = client.leaderboard(2023, 42)?;
+        assert_eq!(42, leaderboard.owner_id);
+        assert_eq!(20, leaderboard.members["42"].local_score);
+        assert_eq!(None, leaderboard.members["7"].name);
+        assert!(client.leaderboard(2023, 43).is_err());
+        assert!(Client::new(server.base_url(), "")
+            .leaderboard(2023, 42)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_limit() -> miette::Result<()> {
+        let server = MockServer::start(
+            "127.0.0.1:0",
+            site("aoc-test-rate-limit")?,
+            Duration::from_secs(90),
+        )?;
+        let client = Client::new(server.base_url(), "abc");
+
+        assert_eq!(Feedback::TooHigh, client.submit(2023, 5, 1, "40")?);
+        assert!(matches!(
+            client.submit(2023, 5, 1, "35")?,
+            Feedback::RateLimited { wait: Some(wait) } if wait.starts_with("1m ")
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_feedback() {
+        assert_eq!(
+            Feedback::RateLimited {
+                wait: Some("48s".to_string())
+            },
+            Feedback::parse("<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 48s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article></main>").unwrap()
+        );
+        assert!(Feedback::parse("<html></html>").is_err());
+    }
+}
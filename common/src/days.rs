@@ -0,0 +1,78 @@
+use std::{
+    io::BufRead,
+    time::{Duration, Instant},
+};
+
+/// Solves a part from its whole input.
+pub type Process = fn(&str) -> miette::Result<String>;
+
+/// Solves a part by reading its input line by line.
+pub type ProcessReader = fn(&mut dyn BufRead) -> miette::Result<String>;
+
+/// Solves a part in two steps, timing the parser and the solver separately.
+pub type ProcessTimed = fn(&str) -> miette::Result<Timed>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timed {
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Runs `parse` and then `solve` on its result, for [`Solution::process_timed`].
+pub fn timed<M, E>(
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<M, E>,
+    solve: impl FnOnce(&M) -> Result<String, E>,
+) -> miette::Result<Timed>
+where
+    miette::Report: From<E>,
+{
+    let start = Instant::now();
+    let model = parse(input)?;
+    let parsed = Instant::now();
+    let answer = solve(&model)?;
+    Ok(Timed {
+        answer,
+        parse: parsed - start,
+        solve: parsed.elapsed(),
+    })
+}
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub process: Process,
+    /// Only set for the days whose input can be processed one line at a time.
+    pub process_reader: Option<ProcessReader>,
+    /// Only set for the days that parse the input into a model before solving.
+    pub process_timed: Option<ProcessTimed>,
+}
+
+/// Solutions that count lines, for testing the runner without any real day.
+#[cfg(test)]
+pub(crate) const LINE_COUNTS: &[Solution] = &[
+    Solution {
+        day: 4,
+        part: 1,
+        process: |input| Ok(input.lines().count().to_string()),
+        process_reader: None,
+        process_timed: None,
+    },
+    Solution {
+        day: 4,
+        part: 2,
+        process: |input| Ok(input.lines().count().to_string()),
+        process_reader: Some(|reader| Ok(reader.lines().count().to_string())),
+        process_timed: Some(|input| {
+            timed(
+                input,
+                |input| {
+                    Ok::<_, miette::Report>(input.lines().map(str::to_string).collect::<Vec<_>>())
+                },
+                |lines| Ok(lines.len().to_string()),
+            )
+        }),
+    },
+];
@@ -0,0 +1,245 @@
+use std::{fs, path::Path};
+
+use miette::miette;
+
+/// The example a part's puzzle description walks through, with the answer it arrives at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+impl Example {
+    /// The contents of the day crate's `examples/part{N}-example.txt` for this example.
+    pub fn fixture(&self) -> String {
+        format!("answer: {}\n---\n{}", self.answer, self.input)
+    }
+
+    /// Reads an example back from a day crate's `examples/part{N}-*.txt`, for its tests.
+    ///
+    /// The files start with `key: value` headers, of which only `answer` is used,
+    /// followed by a `---` line and then the input itself.
+    pub fn load(path: &Path) -> Self {
+        let part = path
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("part")?.split_once('-'))
+            .and_then(|(part, _)| part.parse().ok())
+            .unwrap_or_else(|| panic!("{} isn't named after its part", path.display()));
+        let contents = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("couldn't read {}: {e}", path.display()));
+        let (headers, input) = contents
+            .split_once("---\n")
+            .unwrap_or_else(|| panic!("{} has no `---` line after its headers", path.display()));
+        let answer = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("answer:"))
+            .unwrap_or_else(|| panic!("{} has no `answer` header", path.display()))
+            .trim()
+            .to_string();
+        Self {
+            part,
+            input: input.to_string(),
+            answer,
+        }
+    }
+}
+
+/// Finds the examples in a saved puzzle page, one for every part it describes.
+///
+/// Each part is an `<article>`, its example input is its first `<pre><code>` block and
+/// its answer the last emphasised `<code>`. Part two mostly reuses part one's input,
+/// so without its own block it gets that one.
+pub fn extract(html: &str) -> miette::Result<Vec<Example>> {
+    let articles = elements(html, "<article", "</article>");
+    if articles.is_empty() {
+        return Err(miette!("the page has no puzzle description in it"));
+    }
+
+    let mut examples: Vec<Example> = Vec::new();
+    for (part, article) in (1..).zip(articles) {
+        let input = match elements(article, "<pre><code>", "</code></pre>").first() {
+            Some(block) => text(block),
+            None => examples
+                .last()
+                .map(|example| example.input.clone())
+                .ok_or_else(|| miette!("part {part}'s description has no example input"))?,
+        };
+        let answer = last_emphasised_code(article)
+            .ok_or_else(|| miette!("part {part}'s description has no emphasised answer"))?;
+        examples.push(Example {
+            part,
+            input,
+            answer,
+        });
+    }
+    Ok(examples)
+}
+
+/// The insides of every `open ... close` element, in order.
+fn elements<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let inside = &rest[start + open.len()..];
+        // `open` can stop before the tag's attributes
+        let inside = if open.ends_with('>') {
+            inside
+        } else {
+            inside.split_once('>').map_or("", |(_, inside)| inside)
+        };
+        let Some(end) = inside.find(close) else {
+            break;
+        };
+        found.push(&inside[..end]);
+        rest = &inside[end + close.len()..];
+    }
+    found
+}
+
+/// The puzzles write the numbers that matter as `<code><em>`, sometimes the other way around.
+fn last_emphasised_code(article: &str) -> Option<String> {
+    let position = |open: &str| {
+        article
+            .rfind(open)
+            .map(|start| (start, &article[start + open.len()..]))
+    };
+    let (_, rest, close) = [
+        position("<code><em>").map(|(start, rest)| (start, rest, "</em></code>")),
+        position("<em><code>").map(|(start, rest)| (start, rest, "</code></em>")),
+    ]
+    .into_iter()
+    .flatten()
+    .max_by_key(|&(start, _, _)| start)?;
+    rest.split_once(close).map(|(answer, _)| text(answer))
+}
+
+/// Drops the tags and decodes the entities, leaving what the browser would show.
+fn text(html: &str) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    let mut decoded = String::with_capacity(stripped.len());
+    let mut rest = stripped.as_str();
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .and_then(|end| Some((entity(&rest[1..end])?, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::IntoDiagnostic;
+
+    use super::*;
+
+    #[test]
+    fn test_extract() -> miette::Result<()> {
+        let page = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 5: If You Give A Seed A Fertilizer ---</h2>
+<p>For example:</p>
+<pre><code>seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+</code></pre>
+<p>Seed number <code>79</code> corresponds to soil number <code><em>81</em></code>.</p>
+<p>So, the lowest location number in this example is <code><em>35</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>993500720</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the above example, the lowest location number can be obtained from seed number <code>82</code>, which corresponds to location <em><code>46</code></em>.</p>
+</article>
+<p>Your puzzle answer was <code>4917124</code>.</p>
+</main>
+</body>
+</html>
+"#;
+        let examples = extract(page)?;
+        let input = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        assert_eq!(
+            vec![
+                Example {
+                    part: 1,
+                    input: input.to_string(),
+                    answer: "35".to_string(),
+                },
+                Example {
+                    part: 2,
+                    input: input.to_string(),
+                    answer: "46".to_string(),
+                },
+            ],
+            examples
+        );
+        assert_eq!(format!("answer: 35\n---\n{input}"), examples[0].fixture());
+        assert!(extract("<html></html>").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load() -> miette::Result<()> {
+        let example = Example {
+            part: 2,
+            input: "seeds: 79 14\n".to_string(),
+            answer: "46".to_string(),
+        };
+        let path = std::env::temp_dir().join("part2-example.txt");
+        fs::write(&path, example.fixture()).into_diagnostic()?;
+        assert_eq!(example, Example::load(&path));
+        Ok(())
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            "a &lt; b, 'x' > y & z",
+            text("a &amp;lt; b, &#39;x&#x27; <em>&gt;</em> y & z")
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use std::io::Write;
+
+/// The knobs `aoc gen` exposes, each day maps them onto its own generator options.
+#[derive(Debug, Clone, Default)]
+pub struct Knobs {
+    /// How big the input is, in lines for most days, the grid's width and height
+    /// for 2023's day 3 and the ranges per map for its day 5.
+    pub size: Option<usize>,
+    /// How crowded 2023's day 3 grid is, from 0 to 1.
+    pub density: Option<f64>,
+}
+
+/// Writes a random input for a day, the same seed always giving the same input.
+pub type Generate =
+    fn(day: u8, seed: u64, knobs: &Knobs, writer: &mut dyn Write) -> miette::Result<()>;
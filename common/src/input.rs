@@ -0,0 +1,13 @@
+/// Inputs bigger than this many bytes are streamed when the solution supports it.
+pub const STREAM_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum InputMode {
+    /// Stream large inputs if the solution can process them line by line.
+    #[default]
+    Auto,
+    /// Always stream the input, failing for solutions that can't.
+    Stream,
+    /// Always load the whole input into memory first.
+    Whole,
+}
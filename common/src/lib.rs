@@ -0,0 +1,22 @@
+pub mod answers;
+pub mod bench;
+pub mod build;
+pub mod cache;
+pub mod cli;
+pub mod client;
+pub mod days;
+pub mod examples;
+pub mod generate;
+pub mod input;
+pub mod mock;
+pub mod panics;
+pub mod parallel;
+pub mod readme;
+pub mod repl;
+pub mod run;
+pub mod stream;
+pub mod submissions;
+pub mod testing;
+pub mod verify;
+pub mod watch;
+pub mod year;
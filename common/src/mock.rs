@@ -0,0 +1,265 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::PathBuf,
+    sync::Arc,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use miette::miette;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::answers::Answers;
+
+/// A stand-in for the site that serves everything from `dir`.
+///
+/// `{year}/day-{day:02}.txt` is a day's input, `{year}/leaderboard-{id}.json` a
+/// private leaderboard and `answers.txt` an answers registry with the right answers,
+/// whatever inputs they were recorded for. Submissions are answered with the same
+/// pages the site answers them with, rate limits included.
+pub struct MockServer {
+    server: Arc<Server>,
+    base_url: String,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts serving on `addr` in the background, port 0 picks any free port.
+    ///
+    /// After a wrong answer every submission is rate limited for `cooldown`.
+    pub fn start(addr: &str, dir: PathBuf, cooldown: Duration) -> miette::Result<Self> {
+        let answers = Answers::load(&dir.join("answers.txt"))?;
+        let server = Arc::new(Server::http(addr).map_err(|e| miette!("listen on {addr}: {e}"))?);
+        let port = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| miette!("{addr} isn't an IP address"))?
+            .port();
+        let mut site = Site {
+            dir,
+            answers,
+            cooldown,
+            solved: BTreeSet::new(),
+            locked_until: None,
+        };
+        let thread = std::thread::spawn({
+            let server = server.clone();
+            move || {
+                for request in server.incoming_requests() {
+                    site.respond(request);
+                }
+            }
+        });
+        Ok(Self {
+            server,
+            base_url: format!("http://127.0.0.1:{port}"),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Serves until the process is stopped.
+    pub fn join(mut self) {
+        if let Some(thread) = self.thread.take() {
+            thread.join().expect("the server to never panic");
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            thread.join().expect("the server to never panic");
+        }
+    }
+}
+
+struct Site {
+    dir: PathBuf,
+    answers: Answers,
+    cooldown: Duration,
+    /// Every `(year, day, part)` that was answered right.
+    solved: BTreeSet<(u16, u8, u8)>,
+    /// When answers are taken again after a wrong one.
+    locked_until: Option<Instant>,
+}
+
+impl Site {
+    fn respond(&mut self, mut request: Request) {
+        let (status, content_type, body) = self.handle(&mut request);
+        let content_type = Header::from_bytes("Content-Type", content_type)
+            .expect("content types to be valid headers");
+        // the client hanging up early is its own problem
+        let _ = request.respond(
+            Response::from_string(body)
+                .with_status_code(status)
+                .with_header(content_type),
+        );
+    }
+
+    fn handle(&mut self, request: &mut Request) -> (u16, &'static str, String) {
+        let has_session = request.headers().iter().any(|header| {
+            header.field.equiv("Cookie")
+                && header
+                    .value
+                    .as_str()
+                    .split(';')
+                    .filter_map(|cookie| cookie.trim().strip_prefix("session="))
+                    .any(|session| !session.is_empty())
+        });
+        let path = request.url().to_string();
+        let segments = path.trim_start_matches('/').split('/').collect::<Vec<_>>();
+        let method = request.method().clone();
+        let not_found = (404, "text/plain", "404 Not Found\n".to_string());
+        match (method, segments.as_slice()) {
+            _ if !has_session => (
+                400,
+                "text/plain",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_string(),
+            ),
+            (Method::Get, [year, "day", day, "input"]) => {
+                match (year.parse::<u16>(), day.parse::<u8>()) {
+                    (Ok(year), Ok(day)) => self
+                        .read(&format!("{year}/day-{day:02}.txt"))
+                        .map_or(not_found, |input| (200, "text/plain", input)),
+                    _ => not_found,
+                }
+            }
+            (Method::Post, [year, "day", day, "answer"]) => {
+                let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+                    return not_found;
+                };
+                let mut form = String::new();
+                if request.as_reader().read_to_string(&mut form).is_err() {
+                    return (400, "text/plain", "400 Bad Request\n".to_string());
+                }
+                let field = |name| {
+                    form.split('&')
+                        .filter_map(|pair| pair.split_once('='))
+                        .find(|(key, _)| *key == name)
+                        .map(|(_, value)| decode(value))
+                };
+                match (
+                    field("level").and_then(|level| level.parse().ok()),
+                    field("answer"),
+                ) {
+                    (Some(part), Some(answer)) => (
+                        200,
+                        "text/html",
+                        page(year, day, &self.submit(year, day, part, &answer)),
+                    ),
+                    _ => (400, "text/plain", "400 Bad Request\n".to_string()),
+                }
+            }
+            (Method::Get, [year, "leaderboard", "private", "view", file]) => {
+                match (year.parse::<u16>(), file.strip_suffix(".json")) {
+                    (Ok(year), Some(id)) => self
+                        .read(&format!("{year}/leaderboard-{id}.json"))
+                        .map_or(not_found, |json| (200, "application/json", json)),
+                    _ => not_found,
+                }
+            }
+            _ => not_found,
+        }
+    }
+
+    fn read(&self, path: &str) -> Option<String> {
+        fs::read_to_string(self.dir.join(path)).ok()
+    }
+
+    /// The article the site would answer this submission with.
+    fn submit(&mut self, year: u16, day: u8, part: u8, answer: &str) -> String {
+        let back = format!("<a href=\"/{year}/day/{day}\">[Return to Day {day}]</a>");
+        let unlocked = part == 1 || self.solved.contains(&(year, day, 1));
+        let Some(known) = self
+            .answers
+            .get(year, day, part)
+            .filter(|_| unlocked && !self.solved.contains(&(year, day, part)))
+        else {
+            return format!("You don't seem to be solving the right level.  Did you already complete it? {back}");
+        };
+
+        let now = Instant::now();
+        if let Some(until) = self.locked_until.filter(|&until| until > now) {
+            let left = (until - now).as_secs().max(1);
+            let left = if left >= 60 {
+                format!("{}m {}s", left / 60, left % 60)
+            } else {
+                format!("{left}s")
+            };
+            return format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {left} left to wait. {back}");
+        }
+
+        if known.answer == answer {
+            self.solved.insert((year, day, part));
+            let next = if part == 1 {
+                format!("<a href=\"/{year}/day/{day}#part2\">[Continue to Part Two]</a>")
+            } else {
+                format!("<a href=\"/{year}\">[Return to Advent calendar]</a>")
+            };
+            return format!(
+                "That's the right answer!  You are one gold star closer to saving the day. {next}"
+            );
+        }
+
+        self.locked_until = Some(now + self.cooldown);
+        let hint = match (answer.parse::<i128>(), known.answer.parse::<i128>()) {
+            (Ok(answer), Ok(known)) if answer > known => "; your answer is too high",
+            (Ok(answer), Ok(known)) if answer < known => "; your answer is too low",
+            _ => "",
+        };
+        format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/{year}/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. {back}")
+    }
+}
+
+/// Wraps an article the way the site's pages do.
+fn page(year: u16, day: u8, article: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en-us\">
+<head>
+<meta charset=\"utf-8\"/>
+<title>Day {day} - Advent of Code {year}</title>
+</head>
+<body>
+<main>
+<article><p>{article}</p></article>
+</main>
+</body>
+</html>
+"
+    )
+}
+
+/// Undoes the form encoding of a submitted field.
+fn decode(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' if tail.len() >= 2 => {
+                match std::str::from_utf8(&tail[..2])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(decoded) => {
+                        bytes.push(decoded);
+                        rest = &tail[2..];
+                        continue;
+                    }
+                    None => bytes.push(byte),
+                }
+            }
+            byte => bytes.push(byte),
+        }
+        rest = tail;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -0,0 +1,111 @@
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// What a caught panic said, and where it was raised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    /// `file:line:column`, with the file relative to the workspace it was built in.
+    pub location: Option<String>,
+}
+
+/// A solution that panicked instead of returning an error.
+#[derive(Error, Diagnostic, Debug)]
+#[error("panicked while solving {input}: {message}")]
+#[diagnostic(code(aoc::panicked))]
+pub struct Panicked {
+    /// The input it was given.
+    pub input: String,
+    pub message: String,
+    #[help]
+    pub location: Option<String>,
+}
+
+impl Panicked {
+    pub fn new(input: String, panic: Panic) -> Self {
+        Self {
+            input,
+            message: panic.message,
+            location: panic
+                .location
+                .map(|location| format!("the panic was raised at {location}")),
+        }
+    }
+}
+
+thread_local! {
+    /// How many calls to [`catch`] this thread is in.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// The last panic the hook kept from this thread.
+    static CAUGHT: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a panic into an error instead of unwinding further.
+///
+/// The panic isn't printed, whoever catches it reports it instead. Panics outside of
+/// `catch` still go to the hook that was installed before.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() == 0 {
+                return previous(info);
+            }
+            let panic = Panic {
+                message: info
+                    .payload_as_str()
+                    .unwrap_or("the panic didn't say why")
+                    .to_string(),
+                location: info.location().map(ToString::to_string),
+            };
+            CAUGHT.set(Some(panic));
+        }));
+    });
+
+    CATCHING.set(CATCHING.get() + 1);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+    result.map_err(|_| {
+        CAUGHT.take().unwrap_or_else(|| Panic {
+            message: "the panic didn't say why".to_string(),
+            location: None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(Ok(3), catch(|| 3));
+
+        let digits = "abc";
+        let panic = catch(|| {
+            digits
+                .chars()
+                .find_map(|c| c.to_digit(10))
+                .expect("at least 1 digit to be present")
+        })
+        .unwrap_err();
+        assert_eq!("at least 1 digit to be present", panic.message);
+        assert!(panic
+            .location
+            .as_deref()
+            .is_some_and(|location| location.starts_with("src/panics.rs:")));
+
+        let panic = catch(|| catch(|| panic!("line {} is empty", 4))).unwrap();
+        assert_eq!(
+            Err("line 4 is empty".to_string()),
+            panic.map_err(|p| p.message)
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    collections::BTreeMap,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::panics::{self, Panic};
+
+/// Some work that runs on a worker thread of its own.
+pub type Task<T> = Box<dyn FnOnce() -> miette::Result<T> + Send>;
+
+/// How a task ended.
+#[derive(Debug)]
+pub enum Status<T> {
+    Done(miette::Result<T>),
+    Panicked(Panic),
+    /// The task was still running when its time ran out, and was left behind.
+    TimedOut(Duration),
+}
+
+/// Runs `tasks` with up to `jobs` of them at once, giving each `timeout` to finish,
+/// and hands every status to `done` in the order of the tasks.
+///
+/// A thread can't be stopped from the outside, so a task that times out keeps its
+/// thread busy until it finishes or the process exits, but no longer takes up a job.
+pub fn run<T: Send + 'static>(
+    tasks: Vec<Task<T>>,
+    jobs: usize,
+    timeout: Duration,
+    mut done: impl FnMut(usize, Status<T>),
+) {
+    let total = tasks.len();
+    let (sender, receiver) = mpsc::channel();
+    let mut pending = tasks.into_iter().enumerate();
+    let mut running = BTreeMap::new();
+    let mut finished = BTreeMap::new();
+    let mut next = 0;
+    while next < total {
+        while running.len() < jobs.max(1) {
+            let Some((index, task)) = pending.next() else {
+                break;
+            };
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let status = match panics::catch(task) {
+                    Ok(result) => Status::Done(result),
+                    Err(panic) => Status::Panicked(panic),
+                };
+                // nobody listens for tasks that timed out
+                let _ = sender.send((index, status));
+            });
+            running.insert(index, Instant::now() + timeout);
+        }
+
+        let deadline = running.values().min().copied();
+        let received = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => Err(mpsc::RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok((index, status)) => {
+                if running.remove(&index).is_some() {
+                    finished.insert(index, status);
+                }
+            }
+            Err(_) => {
+                let now = Instant::now();
+                let expired = running
+                    .iter()
+                    .filter(|&(_, &deadline)| deadline <= now)
+                    .map(|(&index, _)| index)
+                    .collect::<Vec<_>>();
+                for index in expired {
+                    running.remove(&index);
+                    finished.insert(index, Status::TimedOut(timeout));
+                }
+            }
+        }
+
+        while let Some(status) = finished.remove(&next) {
+            done(next, status);
+            next += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::miette;
+
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let tasks: Vec<Task<u32>> = vec![
+            Box::new(|| {
+                thread::sleep(Duration::from_millis(50));
+                Ok(1)
+            }),
+            Box::new(|| Err(miette!("bad input"))),
+            Box::new(|| panic!("at least 1 digit to be present")),
+            Box::new(|| {
+                thread::sleep(Duration::from_secs(5));
+                Ok(4)
+            }),
+            Box::new(|| Ok(5)),
+        ];
+        let mut statuses = Vec::new();
+        let start = Instant::now();
+        run(tasks, 2, Duration::from_millis(500), |index, status| {
+            statuses.push((index, status));
+        });
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        assert_eq!(
+            vec![0, 1, 2, 3, 4],
+            statuses.iter().map(|(index, _)| *index).collect::<Vec<_>>()
+        );
+        assert!(matches!(statuses[0].1, Status::Done(Ok(1))));
+        assert!(matches!(statuses[1].1, Status::Done(Err(_))));
+        assert!(
+            matches!(&statuses[2].1, Status::Panicked(panic) if panic.message == "at least 1 digit to be present")
+        );
+        assert!(matches!(statuses[3].1, Status::TimedOut(_)));
+        assert!(matches!(statuses[4].1, Status::Done(Ok(5))));
+    }
+}
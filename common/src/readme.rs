@@ -0,0 +1,107 @@
+use std::{fmt::Write, path::PathBuf};
+
+use miette::miette;
+
+use crate::{bench::Benches, year::Year};
+
+/// The README section between these two lines belongs to `aoc report readme`,
+/// every year has its own.
+pub fn markers(year: u16) -> (String, String) {
+    (
+        format!("<!-- aoc report readme {year}: start -->"),
+        format!("<!-- aoc report readme {year}: end -->"),
+    )
+}
+
+/// The top-level README of the repository.
+pub fn default_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "README.md"]
+        .iter()
+        .collect()
+}
+
+/// A markdown table with a row per day of `year`, its solved parts and how long they take.
+pub fn table(year: &Year, benches: &Benches) -> String {
+    let mut table = String::from("| Day | Puzzle | Solved | Part 1 | Part 2 |\n");
+    table.push_str("| ---: | --- | --- | ---: | ---: |\n");
+    for &(day, title) in year.titles {
+        let solved = |part| year.find(day, part).is_some();
+        let stars = [1, 2].iter().filter(|&&part| solved(part)).count();
+        let median = |part| match benches.get(year.year, day, part) {
+            Some(bench) if solved(part) => format!("{:.2} ms", bench.median_ms),
+            _ => "-".to_string(),
+        };
+        writeln!(
+            table,
+            "| {day} | [{title}](https://adventofcode.com/{}/day/{day}) | {} | {} | {} |",
+            year.year,
+            "⭐".repeat(stars),
+            median(1),
+            median(2)
+        )
+        .unwrap();
+    }
+    table
+}
+
+/// Puts `table` between the markers of `year` in `readme`, replacing whatever was there.
+pub fn update(readme: &str, year: u16, table: &str) -> miette::Result<String> {
+    let (start_marker, end_marker) = markers(year);
+    let missing =
+        || miette!("the README needs a `{start_marker}` line and an `{end_marker}` line after it");
+    let start = readme.find(&start_marker).ok_or_else(missing)? + start_marker.len();
+    let end = start + readme[start..].find(&end_marker).ok_or_else(missing)?;
+    Ok(format!(
+        "{}\n\n{table}\n{}",
+        &readme[..start],
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bench::Bench, days::LINE_COUNTS};
+
+    #[test]
+    fn test_update() -> miette::Result<()> {
+        let year = Year {
+            year: 2023,
+            dir: ".",
+            solutions: LINE_COUNTS,
+            titles: &[(4, "Scratchcards")],
+            sources: &[],
+            generate: None,
+            repl: None,
+        };
+        let benches = Benches {
+            benches: vec![Bench {
+                year: 2023,
+                day: 4,
+                part: 2,
+                runs: 3,
+                median_ms: 1.234,
+            }],
+        };
+        let table = table(&year, &benches);
+        assert_eq!(
+            "| 4 | [Scratchcards](https://adventofcode.com/2023/day/4) | ⭐⭐ | - | 1.23 ms |",
+            table.lines().nth(2).unwrap()
+        );
+
+        let (start, end) = markers(2023);
+        let (other_start, other_end) = markers(2022);
+        let readme =
+            format!("# Advent of Code\n{other_start}\n{other_end}\n{start}\nold\n{end}\nmore\n");
+        let updated = update(&readme, 2023, &table)?;
+        assert_eq!(
+            format!(
+                "# Advent of Code\n{other_start}\n{other_end}\n{start}\n\n{table}\n{end}\nmore\n"
+            ),
+            updated
+        );
+        assert_eq!(updated, update(&updated, 2023, &table)?);
+        assert!(update(&updated, 2024, &table).is_err());
+        Ok(())
+    }
+}
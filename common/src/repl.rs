@@ -0,0 +1,94 @@
+use std::io::{BufRead, Write};
+
+use miette::IntoDiagnostic;
+
+/// Answers a query about the input a session parsed.
+pub type Query = Box<dyn Fn(&str) -> miette::Result<String>>;
+
+/// A day's input, parsed once, answering one query after the other.
+pub struct Session {
+    /// Every query with its arguments and what it shows, for `help`.
+    pub commands: &'static [(&'static str, &'static str)],
+    pub query: Query,
+}
+
+/// Parses a day's input into a session, for the days that can be queried.
+pub type Open = fn(day: u8, input: &str) -> miette::Result<Session>;
+
+/// Reads queries from `input` until it ends or says `quit`, writing every answer to
+/// `output`. A query that fails just shows why, the session goes on.
+pub fn repl(
+    session: &Session,
+    prompt: &str,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> miette::Result<()> {
+    let mut line = String::new();
+    loop {
+        write!(output, "{prompt}> ").into_diagnostic()?;
+        output.flush().into_diagnostic()?;
+        line.clear();
+        if input.read_line(&mut line).into_diagnostic()? == 0 {
+            return writeln!(output).into_diagnostic();
+        }
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => return Ok(()),
+            "help" => {
+                let width = session
+                    .commands
+                    .iter()
+                    .map(|(command, _)| command.len())
+                    .max()
+                    .unwrap_or_default();
+                for (command, about) in session.commands {
+                    writeln!(output, "{command:<width$}  {about}").into_diagnostic()?;
+                }
+                writeln!(output, "{:<width$}  stop", "quit").into_diagnostic()?;
+            }
+            query => match (session.query)(query) {
+                Ok(answer) => writeln!(output, "{answer}").into_diagnostic()?,
+                Err(report) => writeln!(output, "{report:?}").into_diagnostic()?,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::miette;
+
+    use super::*;
+
+    #[test]
+    fn test_repl() -> miette::Result<()> {
+        let session = Session {
+            commands: &[("line <n>", "the nth line")],
+            query: Box::new(|query| match query.split_once(' ') {
+                Some(("line", n)) => Ok(format!("line {n}")),
+                _ => Err(miette!("`{query}` isn't a query")),
+            }),
+        };
+        let mut output = Vec::new();
+        repl(
+            &session,
+            "2023 day 4",
+            "line 3\n\nhelp\nbogus\nquit\nline 4\n".as_bytes(),
+            &mut output,
+        )?;
+        let output = String::from_utf8(output).into_diagnostic()?;
+        assert!(output.starts_with(
+            "2023 day 4> line 3\n2023 day 4> 2023 day 4> line <n>  the nth line\nquit      stop\n"
+        ));
+        assert!(output.contains("`bogus` isn't a query"));
+        assert!(!output.contains("line 4"));
+
+        let mut output = Vec::new();
+        repl(&session, "2023 day 4", "line 5".as_bytes(), &mut output)?;
+        assert_eq!(
+            "2023 day 4> line 5\n2023 day 4> \n",
+            String::from_utf8(output).into_diagnostic()?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "dhat-heap")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use miette::{miette, Context, IntoDiagnostic};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    answers::{Answers, Verdict},
+    cache::{self, Cache},
+    days::Solution,
+    input::{InputMode, STREAM_THRESHOLD},
+    panics::{self, Panicked},
+    year::Year,
+};
+
+/// How `aoc run` prints what it found.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Just the answer.
+    #[default]
+    Text,
+    /// One JSON object per part with the answer, timings and verdict.
+    Json,
+}
+
+/// Heap usage while solving, only measured with the `dhat-heap` feature. The
+/// counters are process wide, so `run --all` solves one part at a time with it,
+/// though a part left running after timing out still counts toward later ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Allocations {
+    pub blocks: u64,
+    pub bytes: u64,
+}
+
+/// Everything measured while solving one part.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Only set for the solutions that parse separately from solving.
+    pub parse_ms: Option<f64>,
+    /// How long solving took, including the parsing that isn't timed on its own.
+    pub solve_ms: f64,
+    pub allocations: Option<Allocations>,
+    pub input_sha256: String,
+    /// The recorded answer for this input, if there is one.
+    pub expected: Option<String>,
+    pub verdict: Verdict,
+    /// Whether the answer and timings come from the cache instead of solving again.
+    pub cached: bool,
+}
+
+impl Run {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("runs to always serialize")
+    }
+}
+
+impl Display for Run {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} day {} part {}: {} ({:.2} ms",
+            self.year,
+            self.day,
+            self.part,
+            self.answer,
+            self.parse_ms.unwrap_or_default() + self.solve_ms
+        )?;
+        if self.cached {
+            write!(f, ", cached")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Solves `solution` for the input at `path` and checks the answer against `answers`.
+pub fn run_file(
+    year: u16,
+    solution: &Solution,
+    path: &Path,
+    mode: InputMode,
+    answers: &Answers,
+) -> miette::Result<Run> {
+    let file = File::open(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("open {}", path.display()))?;
+    // a panicking solution is just another part that failed
+    let panicked = |panic| Panicked::new(path.display().to_string(), panic);
+
+    let stream = match mode {
+        InputMode::Whole => false,
+        InputMode::Stream => true,
+        InputMode::Auto => {
+            let len = file.metadata().into_diagnostic()?.len();
+            solution.process_reader.is_some() && len > STREAM_THRESHOLD
+        }
+    };
+
+    let (input_sha256, heap_before, timed) = if stream {
+        let process_reader = solution.process_reader.ok_or_else(|| {
+            miette!(
+                "{year} day {} part {} can't be streamed",
+                solution.day,
+                solution.part
+            )
+        })?;
+        // hashing needs its own pass, the solution only sees the input once
+        let input_sha256 = sha256_file(path)?;
+
+        let heap_before = heap_totals();
+        let start = Instant::now();
+        let answer =
+            panics::catch(|| process_reader(&mut BufReader::new(file))).map_err(panicked)??;
+        (input_sha256, heap_before, (answer, None, start.elapsed()))
+    } else {
+        let mut input = String::new();
+        BufReader::new(file)
+            .read_to_string(&mut input)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {}", path.display()))?;
+
+        let heap_before = heap_totals();
+        let timed = match solution.process_timed {
+            Some(process_timed) => {
+                let timed = panics::catch(|| process_timed(&input)).map_err(panicked)??;
+                (timed.answer, Some(timed.parse), timed.solve)
+            }
+            None => {
+                let start = Instant::now();
+                let answer = panics::catch(|| (solution.process)(&input)).map_err(panicked)??;
+                (answer, None, start.elapsed())
+            }
+        };
+        (format!("{:x}", Sha256::digest(&input)), heap_before, timed)
+    };
+    let (answer, parse, solve) = timed;
+
+    let allocations = heap_before
+        .zip(heap_totals())
+        .map(|(before, after)| Allocations {
+            blocks: after.blocks - before.blocks,
+            bytes: after.bytes - before.bytes,
+        });
+    let verdict = answers.verdict(year, solution.day, solution.part, &input_sha256, &answer);
+    Ok(Run {
+        year,
+        day: solution.day,
+        part: solution.part,
+        expected: answers
+            .expected(year, solution.day, solution.part, &input_sha256)
+            .map(str::to_string),
+        answer,
+        parse_ms: parse.map(millis),
+        solve_ms: millis(solve),
+        allocations,
+        input_sha256,
+        verdict,
+        cached: false,
+    })
+}
+
+/// Like [`run_file`], but answers from `cache` when neither the input nor the day's
+/// sources changed since the part was last solved, and caches what it does solve.
+pub fn run_cached(
+    year: &Year,
+    solution: &Solution,
+    path: &Path,
+    mode: InputMode,
+    answers: &Answers,
+    cache: &Cache,
+) -> miette::Result<Run> {
+    let Some(source_hash) = year.source_hash(solution.day) else {
+        return run_file(year.year, solution, path, mode, answers);
+    };
+    let input_sha256 = sha256_file(path)?;
+    let key = cache::Key {
+        year: year.year,
+        day: solution.day,
+        part: solution.part,
+        input_sha256: &input_sha256,
+        source_hash,
+    };
+    if let Some(entry) = cache.get(&key) {
+        return Ok(Run {
+            year: year.year,
+            day: solution.day,
+            part: solution.part,
+            expected: answers
+                .expected(year.year, solution.day, solution.part, &input_sha256)
+                .map(str::to_string),
+            verdict: answers.verdict(
+                year.year,
+                solution.day,
+                solution.part,
+                &input_sha256,
+                &entry.answer,
+            ),
+            answer: entry.answer,
+            parse_ms: entry.parse_ms,
+            solve_ms: entry.solve_ms,
+            allocations: None,
+            input_sha256,
+            cached: true,
+        });
+    }
+
+    let run = run_file(year.year, solution, path, mode, answers)?;
+    // the input can change between hashing it here and solving it
+    if run.input_sha256 == input_sha256 {
+        cache.insert(
+            &key,
+            &cache::Entry {
+                answer: run.answer.clone(),
+                parse_ms: run.parse_ms,
+                solve_ms: run.solve_ms,
+            },
+        )?;
+    }
+    Ok(run)
+}
+
+fn sha256_file(path: &Path) -> miette::Result<String> {
+    let file = File::open(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut BufReader::new(file), &mut hasher)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("read {}", path.display()))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Whether a [`HeapProfiler`] is running, dhat panics when asked for stats without one.
+#[cfg(feature = "dhat-heap")]
+static HEAP_PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// dhat's heap profiler, which [`Allocations`] are measured with while it's alive.
+#[cfg(feature = "dhat-heap")]
+pub struct HeapProfiler {
+    _profiler: dhat::Profiler,
+}
+
+#[cfg(feature = "dhat-heap")]
+impl HeapProfiler {
+    pub fn start() -> Self {
+        let profiler = dhat::Profiler::new_heap();
+        HEAP_PROFILING.store(true, Ordering::SeqCst);
+        Self {
+            _profiler: profiler,
+        }
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+impl Drop for HeapProfiler {
+    fn drop(&mut self) {
+        HEAP_PROFILING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Everything allocated so far, only tracked while a [`HeapProfiler`] is running.
+#[cfg(feature = "dhat-heap")]
+fn heap_totals() -> Option<Allocations> {
+    HEAP_PROFILING.load(Ordering::SeqCst).then(|| {
+        let stats = dhat::HeapStats::get();
+        Allocations {
+            blocks: stats.total_blocks,
+            bytes: stats.total_bytes,
+        }
+    })
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn heap_totals() -> Option<Allocations> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::LINE_COUNTS;
+
+    #[test]
+    fn test_run_file() -> miette::Result<()> {
+        let input = "Card 1\nCard 2\nCard 3\n";
+        let path = std::env::temp_dir().join("aoc-test-run-file.txt");
+        std::fs::write(&path, input).into_diagnostic()?;
+        let input_sha256 = format!("{:x}", Sha256::digest(input));
+        let answers = Answers::parse(&format!("2023 4 2 3 {input_sha256}"))?;
+
+        let [part1, part2] = LINE_COUNTS else {
+            unreachable!()
+        };
+        for mode in [InputMode::Auto, InputMode::Stream, InputMode::Whole] {
+            let run = run_file(2023, part2, &path, mode, &answers)?;
+            assert_eq!("3", run.answer);
+            assert_eq!(input_sha256, run.input_sha256);
+            assert_eq!(Verdict::Pass, run.verdict);
+            assert_eq!(Some("3"), run.expected.as_deref());
+        }
+        let run = run_file(2023, part2, &path, InputMode::Whole, &answers)?;
+        assert!(run.parse_ms.is_some());
+        assert!(run.to_json().contains(r#""verdict":"pass""#));
+
+        let run = run_file(2024, part2, &path, InputMode::Whole, &answers)?;
+        assert_eq!(Verdict::Unknown, run.verdict);
+        let run = run_file(2023, part1, &path, InputMode::Whole, &answers)?;
+        assert_eq!(Verdict::Unknown, run.verdict);
+        assert!(run_file(2023, part1, &path, InputMode::Stream, &answers).is_err());
+
+        let broken = Solution {
+            process: |input| Ok(input.lines().nth(5).expect("a sixth line").to_string()),
+            ..*part1
+        };
+        let report = run_file(2023, &broken, &path, InputMode::Whole, &answers)
+            .wrap_err("process 2023 day 4 part 1")
+            .unwrap_err();
+        let panicked = report.downcast_ref::<Panicked>().expect("a panic");
+        assert_eq!(path.display().to_string(), panicked.input);
+        assert_eq!("a sixth line", panicked.message);
+        assert!(panicked
+            .location
+            .as_deref()
+            .is_some_and(|location| location.contains("src/run.rs:")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_cached() -> miette::Result<()> {
+        let path = std::env::temp_dir().join("aoc-test-run-cached.txt");
+        std::fs::write(&path, "Card 1\nCard 2\nCard 3\n").into_diagnostic()?;
+        let cache = Cache::new(std::env::temp_dir().join("aoc-test-run-cached"));
+        cache.clear()?;
+        let answers = Answers::default();
+        let year = |sources| Year {
+            year: 2023,
+            dir: ".",
+            solutions: LINE_COUNTS,
+            titles: &[],
+            sources,
+            generate: None,
+            repl: None,
+        };
+        let solve = |year: &Year| {
+            run_cached(
+                year,
+                &LINE_COUNTS[1],
+                &path,
+                InputMode::Whole,
+                &answers,
+                &cache,
+            )
+        };
+
+        let solved = solve(&year(&[(4, "abc")]))?;
+        assert!(!solved.cached);
+        let cached = solve(&year(&[(4, "abc")]))?;
+        assert!(cached.cached);
+        assert_eq!(solved.answer, cached.answer);
+        assert_eq!(solved.solve_ms, cached.solve_ms);
+        assert!(!solve(&year(&[(4, "def")]))?.cached);
+        assert!(!solve(&year(&[]))?.cached);
+
+        std::fs::write(&path, "Card 1\n").into_diagnostic()?;
+        let changed = solve(&year(&[(4, "abc")]))?;
+        assert!(!changed.cached);
+        assert_eq!("1", changed.answer);
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use std::io::{self, BufRead};
+
+/// Calls `f` with the 0-based index and contents of every line in `reader`.
+///
+/// Lines are read into a single reused buffer, so memory use is bounded by the
+/// longest line rather than the size of the input. Like [`str::lines`], the
+/// trailing `\n` or `\r\n` is not part of the line.
+pub fn try_for_each_line<R: BufRead, E: From<io::Error>>(
+    mut reader: R,
+    mut f: impl FnMut(usize, &str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut buffer = String::new();
+    let mut index = 0;
+    while reader.read_line(&mut buffer)? != 0 {
+        let line = buffer
+            .strip_suffix('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .unwrap_or(&buffer);
+        f(index, line)?;
+        buffer.clear();
+        index += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_for_each_line() -> io::Result<()> {
+        let mut lines = vec![];
+        try_for_each_line("a\r\nb\n\nc".as_bytes(), |index, line| {
+            lines.push((index, line.to_string()));
+            Ok::<_, io::Error>(())
+        })?;
+        assert_eq!(
+            vec![
+                (0, "a".to_string()),
+                (1, "b".to_string()),
+                (2, String::new()),
+                (3, "c".to_string())
+            ],
+            lines
+        );
+
+        let error = try_for_each_line("a\nb\n".as_bytes(), |index, _| match index {
+            0 => Ok(()),
+            _ => Err(io::Error::other("stop")),
+        });
+        assert_eq!("stop", error.unwrap_err().to_string());
+        Ok(())
+    }
+}
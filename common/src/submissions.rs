@@ -0,0 +1,225 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use miette::{miette, Context, IntoDiagnostic};
+
+use crate::client::Feedback;
+
+/// One answer that was sent to a site, and what it said about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub answer: String,
+    pub feedback: Feedback,
+}
+
+/// Every answer ever submitted, read from `submissions.txt`.
+///
+/// Every non-empty line that doesn't start with `#` is
+/// `site year day part feedback answer`, the site being the base URL that was
+/// submitted to, so a local stand-in never rules out answers for the real site.
+#[derive(Debug, Clone, Default)]
+pub struct Submissions {
+    attempts: BTreeMap<(String, u16, u8, u8), Vec<Attempt>>,
+}
+
+impl Submissions {
+    /// Where the repository keeps its submissions, next to the recorded answers.
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("submissions.txt")
+    }
+
+    /// Reads the log at `path`, a missing file just has no submissions.
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::parse(&contents).wrap_err_with(|| format!("read {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", path.display())),
+        }
+    }
+
+    pub fn parse(contents: &str) -> miette::Result<Self> {
+        let mut submissions = Self::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                miette!(
+                    "line {} isn't `site year day part feedback answer`",
+                    index + 1
+                )
+            };
+            // the answer goes last, it's whatever was submitted
+            let [site, year, day, part, feedback, answer] = line
+                .splitn(6, ' ')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid())?;
+            let year = year.parse().map_err(|_| invalid())?;
+            let day = day.parse().map_err(|_| invalid())?;
+            let part = part.parse().map_err(|_| invalid())?;
+            let feedback = Feedback::from_name(feedback).ok_or_else(invalid)?;
+            submissions.record(site, year, day, part, answer, feedback);
+        }
+        Ok(submissions)
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let mut contents = String::from("# site year day part feedback answer\n");
+        for ((site, year, day, part), attempts) in &self.attempts {
+            for attempt in attempts {
+                contents.push_str(&format!(
+                    "{site} {year} {day} {part} {} {}\n",
+                    attempt.feedback.name(),
+                    attempt.answer
+                ));
+            }
+        }
+        fs::write(path, contents)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("write {}", path.display()))
+    }
+
+    /// Every answer submitted for a part, oldest first.
+    pub fn attempts(&self, site: &str, year: u16, day: u8, part: u8) -> &[Attempt] {
+        self.attempts
+            .get(&(site.to_string(), year, day, part))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn record(
+        &mut self,
+        site: &str,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        feedback: Feedback,
+    ) {
+        self.attempts
+            .entry((site.to_string(), year, day, part))
+            .or_default()
+            .push(Attempt {
+                answer: answer.to_string(),
+                feedback,
+            });
+    }
+
+    /// Refuses answers the site is already known to reject: the part is solved, the
+    /// same answer was wrong before, or it isn't below every answer that was too high
+    /// and above every answer that was too low.
+    pub fn check(
+        &self,
+        site: &str,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> miette::Result<()> {
+        let attempts = self.attempts(site, year, day, part);
+        let refused = |reason: String| {
+            miette!(
+                help = "submit it anyway with --force",
+                "{year} day {day} part {part} {reason}"
+            )
+        };
+        if let Some(solved) = attempts
+            .iter()
+            .find(|attempt| attempt.feedback == Feedback::Correct)
+        {
+            return Err(refused(format!(
+                "is solved already, with {}",
+                solved.answer
+            )));
+        }
+        if let Some(wrong) = attempts
+            .iter()
+            .find(|attempt| attempt.answer == answer && attempt.feedback.is_wrong())
+        {
+            return Err(refused(format!(
+                "was answered {answer} before, and that was {}",
+                wrong.feedback
+            )));
+        }
+
+        let Ok(number) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |feedback: Feedback| {
+            attempts
+                .iter()
+                .filter(move |attempt| attempt.feedback == feedback)
+                .filter_map(|attempt| attempt.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Feedback::TooHigh)
+            .min()
+            .filter(|&high| number >= high)
+        {
+            return Err(refused(format!(
+                "can't be {answer}, {high} was already too high"
+            )));
+        }
+        if let Some(low) = bound(Feedback::TooLow).max().filter(|&low| number <= low) {
+            return Err(refused(format!(
+                "can't be {answer}, {low} was already too low"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SITE: &str = "http://127.0.0.1:8080";
+
+    #[test]
+    fn test_check() -> miette::Result<()> {
+        let mut submissions = Submissions::parse(&format!(
+            "# site year day part feedback answer
+{SITE} 2023 5 1 too_high 40
+{SITE} 2023 5 1 rate_limited 39
+{SITE} 2023 5 1 too_low 30
+{SITE} 2023 5 1 too_high 38
+{SITE} 2023 5 1 wrong thirty five
+"
+        ))?;
+        assert_eq!(5, submissions.attempts(SITE, 2023, 5, 1).len());
+        assert!(submissions.check(SITE, 2023, 5, 1, "35").is_ok());
+        assert!(submissions.check(SITE, 2023, 5, 1, "thirty five").is_err());
+        assert!(submissions.check(SITE, 2023, 5, 1, "thirty six").is_ok());
+        assert!(submissions.check(SITE, 2023, 5, 1, "39").is_err());
+        assert!(submissions.check(SITE, 2023, 5, 1, "38").is_err());
+        assert!(submissions.check(SITE, 2023, 5, 1, "30").is_err());
+        assert!(submissions.check(SITE, 2023, 5, 1, "12").is_err());
+        assert!(submissions.check(SITE, 2023, 5, 2, "40").is_ok());
+        assert!(submissions
+            .check("https://adventofcode.com", 2023, 5, 1, "40")
+            .is_ok());
+
+        submissions.record(SITE, 2023, 5, 1, "35", Feedback::Correct);
+        assert!(submissions.check(SITE, 2023, 5, 1, "36").is_err());
+
+        let path = std::env::temp_dir().join("aoc-test-submissions.txt");
+        submissions.save(&path)?;
+        let saved = Submissions::load(&path)?;
+        assert_eq!(
+            submissions.attempts(SITE, 2023, 5, 1),
+            saved.attempts(SITE, 2023, 5, 1)
+        );
+        assert!(Submissions::parse("2023 5 1 too_high 40").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+//! Assertions the days' tests share.
+
+/// Asserts what a day made of an input that's too big for its default accumulators.
+///
+/// With the day's `u128` feature `$result` has to be `Ok($answer)`, without it an
+/// error that matches `$error`. The feature is the calling crate's, so every day
+/// with a `u128` feature can check both sides of it with one test.
+#[macro_export]
+macro_rules! assert_overflow {
+    ($result:expr, $error:pat $(if $guard:expr)?, u128: $answer:expr $(,)?) => {
+        match $result {
+            Ok(answer) if cfg!(feature = "u128") => assert_eq!($answer, answer),
+            Err($error) if !cfg!(feature = "u128") $(&& $guard)? => {}
+            result => panic!(
+                "expected {}, got {result:?}",
+                if cfg!(feature = "u128") {
+                    $answer
+                } else {
+                    stringify!(Err($error))
+                }
+            ),
+        }
+    };
+}
@@ -0,0 +1,239 @@
+use std::{
+    fmt::{Display, Write},
+    time::{Duration, Instant},
+};
+
+use miette::{GraphicalReportHandler, GraphicalTheme};
+
+use crate::{
+    answers::{Answers, Verdict},
+    days::Solution,
+    input::InputMode,
+    run::{self, Run},
+    year::Year,
+};
+
+/// What checking one part against its recorded answer came to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass(Run),
+    /// The answer differs from the recorded one.
+    Fail(Run),
+    /// Nothing is recorded for this part's input.
+    Unknown(Run),
+    /// Solving failed.
+    Error {
+        message: String,
+        /// The whole diagnostic, rendered the way miette prints it.
+        diagnostic: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub time: Duration,
+    pub outcome: Outcome,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {} part {}: ", self.year, self.day, self.part)?;
+        match &self.outcome {
+            Outcome::Pass(run) => write!(f, "pass, {}", run.answer)?,
+            Outcome::Fail(run) => write!(
+                f,
+                "FAIL, expected {} but got {}",
+                run.expected.as_deref().unwrap_or_default(),
+                run.answer
+            )?,
+            Outcome::Unknown(run) => write!(f, "unknown, {} isn't recorded", run.answer)?,
+            Outcome::Error { message, .. } => write!(f, "ERROR, {message}")?,
+        }
+        write!(f, " ({:.2?})", self.time)
+    }
+}
+
+/// Solves every part of `year` in `solutions` from its own input and checks it against `answers`.
+pub fn verify(year: &Year, solutions: &[Solution], answers: &Answers) -> Vec<Case> {
+    solutions
+        .iter()
+        .map(|solution| {
+            let path = year.input_path(solution.day, solution.part);
+            let start = Instant::now();
+            let outcome = match run::run_file(year.year, solution, &path, InputMode::Auto, answers)
+            {
+                Ok(run) => match run.verdict {
+                    Verdict::Pass => Outcome::Pass(run),
+                    Verdict::Fail => Outcome::Fail(run),
+                    Verdict::Unknown => Outcome::Unknown(run),
+                },
+                Err(report) => Outcome::Error {
+                    message: report.to_string(),
+                    diagnostic: render(&report),
+                },
+            };
+            Case {
+                year: year.year,
+                day: solution.day,
+                part: solution.part,
+                time: start.elapsed(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Renders a diagnostic the way miette prints it, minus the colors.
+fn render(report: &miette::Report) -> String {
+    let mut rendered = String::new();
+    GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+        .render_report(&mut rendered, report.as_ref())
+        .expect("writing to a string to never fail");
+    rendered
+}
+
+/// Writes `cases` as a JUnit XML report with one test case per part.
+///
+/// Wrong answers are failures, solutions that return an error are errors and
+/// parts without a recorded answer are skipped.
+pub fn junit(cases: &[Case]) -> String {
+    let count =
+        |matches: fn(&Outcome) -> bool| cases.iter().filter(|case| matches(&case.outcome)).count();
+    let failures = count(|outcome| matches!(outcome, Outcome::Fail(_)));
+    let errors = count(|outcome| matches!(outcome, Outcome::Error { .. }));
+    let skipped = count(|outcome| matches!(outcome, Outcome::Unknown(_)));
+    let time = cases.iter().map(|case| case.time).sum::<Duration>();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let attributes = format!(
+        "name=\"aoc\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{:.3}\"",
+        cases.len(),
+        time.as_secs_f64()
+    );
+    writeln!(xml, "<testsuites {attributes}>").unwrap();
+    writeln!(xml, "  <testsuite {attributes}>").unwrap();
+    for case in cases {
+        write!(
+            xml,
+            "    <testcase classname=\"{}.day-{:02}\" name=\"part {}\" time=\"{:.3}\"",
+            case.year,
+            case.day,
+            case.part,
+            case.time.as_secs_f64()
+        )
+        .unwrap();
+        match &case.outcome {
+            Outcome::Pass(_) => xml.push_str("/>\n"),
+            Outcome::Fail(run) => {
+                let message = format!(
+                    "expected {} but got {}",
+                    run.expected.as_deref().unwrap_or_default(),
+                    run.answer
+                );
+                let message = escape(&message);
+                writeln!(
+                    xml,
+                    ">\n      <failure message=\"{message}\">{message}</failure>"
+                )
+                .unwrap();
+                xml.push_str("    </testcase>\n");
+            }
+            Outcome::Unknown(run) => {
+                let message = escape(&format!(
+                    "no answer is recorded for input {}",
+                    run.input_sha256
+                ));
+                writeln!(xml, ">\n      <skipped message=\"{message}\"/>").unwrap();
+                xml.push_str("    </testcase>\n");
+            }
+            Outcome::Error {
+                message,
+                diagnostic,
+            } => {
+                writeln!(
+                    xml,
+                    ">\n      <error message=\"{}\">{}</error>",
+                    escape(message),
+                    escape(diagnostic)
+                )
+                .unwrap();
+                xml.push_str("    </testcase>\n");
+            }
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 has no way to write most control characters at all
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(answer: &str, expected: Option<&str>, verdict: Verdict) -> Run {
+        Run {
+            year: 2023,
+            day: 4,
+            part: 2,
+            answer: answer.to_string(),
+            parse_ms: None,
+            solve_ms: 1.0,
+            allocations: None,
+            input_sha256: "abc".to_string(),
+            expected: expected.map(str::to_string),
+            verdict,
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_junit() {
+        let case = |part, outcome| Case {
+            year: 2023,
+            day: 4,
+            part,
+            time: Duration::from_millis(5),
+            outcome,
+        };
+        let cases = [
+            case(1, Outcome::Pass(run("13", Some("13"), Verdict::Pass))),
+            case(2, Outcome::Fail(run("31", Some("30"), Verdict::Fail))),
+            case(3, Outcome::Unknown(run("7", None, Verdict::Unknown))),
+            case(
+                4,
+                Outcome::Error {
+                    message: "couldn't parse <input>".to_string(),
+                    diagnostic: "× couldn't parse <input>\n".to_string(),
+                },
+            ),
+        ];
+        let xml = junit(&cases);
+
+        assert!(xml.contains(r#"tests="4" failures="1" errors="1" skipped="1""#));
+        assert!(xml.contains(r#"<testcase classname="2023.day-04" name="part 1" time="0.005"/>"#));
+        assert!(xml.contains(r#"<failure message="expected 30 but got 31">"#));
+        assert!(xml.contains(r#"<skipped message="no answer is recorded for input abc"/>"#));
+        assert!(xml.contains(
+            r#"<error message="couldn&apos;t parse &lt;input&gt;">× couldn&apos;t parse &lt;input&gt;"#
+        ));
+    }
+}
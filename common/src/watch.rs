@@ -0,0 +1,390 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use miette::{miette, Context, IntoDiagnostic};
+
+use crate::{answers::Verdict, run::Run, year::Year};
+
+/// How the example tests of a part went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Examples {
+    Passed(usize),
+    Failed {
+        passed: usize,
+        failed: usize,
+    },
+    /// The day didn't build, with the first error cargo gave.
+    Broken(String),
+}
+
+/// What clippy said about the day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lints {
+    Clean,
+    /// How many warnings there were, with the first one.
+    Warnings(usize, String),
+    /// Clippy didn't get through, with the first error it gave.
+    Broken(String),
+}
+
+/// What one round of checking a part came to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Status {
+    pub examples: Examples,
+    pub lints: Lints,
+    pub run: Result<Run, String>,
+    /// The answer of the round before, to show whether it changed.
+    pub previous: Option<String>,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.examples {
+            Examples::Passed(passed) => writeln!(f, "examples  ✓ {passed} passed")?,
+            Examples::Failed { passed, failed } => {
+                writeln!(f, "examples  ✗ {failed} failed, {passed} passed")?
+            }
+            Examples::Broken(error) => writeln!(f, "examples  ✗ {error}")?,
+        }
+        match &self.lints {
+            Lints::Clean => writeln!(f, "clippy    ✓ no warnings")?,
+            Lints::Warnings(count, first) => writeln!(f, "clippy    ✗ {count} warnings, {first}")?,
+            Lints::Broken(error) => writeln!(f, "clippy    ✗ {error}")?,
+        }
+        match &self.run {
+            Ok(run) => {
+                let change = match &self.previous {
+                    None => String::new(),
+                    Some(previous) if *previous == run.answer => " (unchanged)".to_string(),
+                    Some(previous) => format!(" (was {previous})"),
+                };
+                let verdict = match run.verdict {
+                    Verdict::Pass => ", right",
+                    Verdict::Fail => ", WRONG",
+                    Verdict::Unknown => "",
+                };
+                writeln!(f, "answer    {}{change}{verdict}", run.answer)?;
+                write!(
+                    f,
+                    "time      {:.2} ms",
+                    run.parse_ms.unwrap_or_default() + run.solve_ms
+                )
+            }
+            Err(error) => write!(f, "answer    ✗ {error}"),
+        }
+    }
+}
+
+/// Checks `part` of `day` every time its crate's sources, examples or input change,
+/// until the process is stopped.
+pub fn watch(year: &Year, day: u8, part: u8, interval: Duration) -> miette::Result<()> {
+    let dir = year.day_dir(day);
+    if !dir.is_dir() {
+        return Err(miette!("{} doesn't exist", dir.display()));
+    }
+    let mut seen = None;
+    let mut previous = None;
+    let mut round = 0;
+    loop {
+        let files = modified(&dir)?;
+        if seen.as_ref() == Some(&files) {
+            thread::sleep(interval);
+            continue;
+        }
+        seen = Some(files);
+        round += 1;
+
+        // clear the terminal first, so the status stays in one place
+        print!("\x1b[2J\x1b[H");
+        println!("{} day {day} part {part}, checking…", year.year);
+        let status = check(year, day, part, previous.clone());
+        print!("\x1b[2J\x1b[H");
+        println!("{} day {day} part {part}, round {round}", year.year);
+        println!("{status}");
+        if let Ok(run) = status.run {
+            previous = Some(run.answer);
+        }
+    }
+}
+
+/// When every file that affects the part's answer last changed.
+///
+/// Snapshots insta writes for failing tests are left out, or every failing round
+/// would start the next one.
+fn modified(dir: &Path) -> miette::Result<BTreeMap<PathBuf, SystemTime>> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let metadata = fs::metadata(&path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("read {}", path.display()))?;
+        if metadata.is_dir() {
+            for entry in fs::read_dir(&path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("read {}", path.display()))?
+            {
+                pending.push(entry.into_diagnostic()?.path());
+            }
+        } else if path.extension().is_none_or(|extension| extension != "new") {
+            files.insert(path, metadata.modified().into_diagnostic()?);
+        }
+    }
+    Ok(files)
+}
+
+/// Rebuilds the day, runs its example tests and clippy, and solves its input.
+fn check(year: &Year, day: u8, part: u8, previous: Option<String>) -> Status {
+    // the runner that is watching can't solve with the sources as they are now,
+    // so every step goes through cargo
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let package = format!("day-{day:02}");
+    let test = |args: &[String]| {
+        Command::new(&cargo)
+            .current_dir(year.dir)
+            .args(["test", "--release", "-p", &package, "--lib", "--"])
+            .args(args)
+            .output()
+    };
+    let examples = match test(&[
+        "--list".to_string(),
+        "--format".to_string(),
+        "terse".to_string(),
+    ]) {
+        Ok(output) if output.status.success() => {
+            // a filter would also run every other test that starts with the same name
+            let mut args = example_tests(&String::from_utf8_lossy(&output.stdout), part);
+            args.push("--exact".to_string());
+            match test(&args) {
+                Ok(output) => examples(
+                    &String::from_utf8_lossy(&output.stdout),
+                    &String::from_utf8_lossy(&output.stderr),
+                ),
+                Err(e) => Examples::Broken(format!("couldn't run cargo: {e}")),
+            }
+        }
+        Ok(output) => Examples::Broken(first_error(&String::from_utf8_lossy(&output.stderr))),
+        Err(e) => Examples::Broken(format!("couldn't run cargo: {e}")),
+    };
+
+    let clippy = Command::new(&cargo)
+        .current_dir(year.dir)
+        .args(["clippy", "-p", &package, "--message-format", "json"])
+        .output();
+    let lints = match clippy {
+        Ok(output) => lints(
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        ),
+        Err(e) => Lints::Broken(format!("couldn't run cargo: {e}")),
+    };
+
+    let solved = Command::new(&cargo)
+        .current_dir(year.dir)
+        .args(["run", "--release", "-q", "-p", "aoc", "--", "run"])
+        .args([year.year.to_string(), day.to_string(), part.to_string()])
+        .args(["--format", "json"])
+        .output();
+    let run = match solved {
+        Ok(output) if output.status.success() => {
+            serde_json::from_slice::<Run>(&output.stdout).map_err(|e| e.to_string())
+        }
+        Ok(output) => Err(first_error(&String::from_utf8_lossy(&output.stderr))),
+        Err(e) => Err(format!("couldn't run cargo: {e}")),
+    };
+
+    Status {
+        examples,
+        lints,
+        run,
+        previous,
+    }
+}
+
+/// The example tests of `part` in the `--list` of a day's tests, which rstest
+/// generates one of for every example file.
+fn example_tests(list: &str, part: u8) -> Vec<String> {
+    let prefix = format!("part{part}::tests::test_process::");
+    list.lines()
+        .filter_map(|line| line.strip_suffix(": test"))
+        .filter(|name| name.starts_with(&prefix))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Counts the warnings in clippy's JSON messages.
+fn lints(stdout: &str, stderr: &str) -> Lints {
+    let mut warnings = vec![];
+    for message in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|line| line["reason"] == "compiler-message")
+    {
+        let message = &message["message"];
+        // the first line of the rendered message is the one that says what's wrong
+        let rendered = message["rendered"]
+            .as_str()
+            .and_then(|rendered| rendered.lines().next())
+            .unwrap_or_default()
+            .to_string();
+        match message["level"].as_str() {
+            Some("error") => return Lints::Broken(rendered),
+            Some("warning") => warnings.push(rendered),
+            _ => {}
+        }
+    }
+    match warnings.first() {
+        Some(first) => Lints::Warnings(warnings.len(), first.clone()),
+        None if stdout.contains(r#""reason":"build-finished","success":false"#) => {
+            Lints::Broken(first_error(stderr))
+        }
+        None => Lints::Clean,
+    }
+}
+
+/// Reads the test counts from `cargo test`'s summary line.
+fn examples(stdout: &str, stderr: &str) -> Examples {
+    let Some(summary) = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("test result: "))
+    else {
+        return Examples::Broken(first_error(stderr));
+    };
+    let count = |label: &str| {
+        summary
+            .split("; ")
+            .find_map(|field| {
+                field
+                    .trim_start_matches(|c: char| !c.is_ascii_digit())
+                    .strip_suffix(label)
+            })
+            .and_then(|count| count.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    match (count(" passed"), count(" failed")) {
+        (passed, 0) => Examples::Passed(passed),
+        (passed, failed) => Examples::Failed { passed, failed },
+    }
+}
+
+/// The line a build or a run failed with, as short as it gets.
+fn first_error(stderr: &str) -> String {
+    stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("error") || line.starts_with('×'))
+        .or_else(|| stderr.lines().map(str::trim).find(|line| !line.is_empty()))
+        .unwrap_or("failed without saying why")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            Examples::Passed(3),
+            examples(
+                "running 3 tests\n...\ntest result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 5 filtered out; finished in 0.01s\n",
+                ""
+            )
+        );
+        assert_eq!(
+            Examples::Failed {
+                passed: 1,
+                failed: 2
+            },
+            examples(
+                "test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s\n",
+                ""
+            )
+        );
+        assert_eq!(
+            Examples::Broken("error[E0425]: cannot find value `x` in this scope".to_string()),
+            examples(
+                "",
+                "   Compiling day-05 v0.1.0\nerror[E0425]: cannot find value `x` in this scope\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_example_tests() {
+        let list = "part1::tests::test_process::path_1_examples_part1_example_txt: test
+part1::tests::test_process_overflow: test
+part1::tests::test_process_reader::path_1_examples_part1_example_txt: test
+part2::tests::test_process::path_1_examples_part2_example_txt: test
+part2::tests::test_process::path_2_examples_part2_other_txt: test
+part2::tests::test_process_matches_copy_loop: test
+";
+        assert_eq!(
+            vec!["part1::tests::test_process::path_1_examples_part1_example_txt"],
+            example_tests(list, 1)
+        );
+        assert_eq!(
+            vec![
+                "part2::tests::test_process::path_1_examples_part2_example_txt",
+                "part2::tests::test_process::path_2_examples_part2_other_txt"
+            ],
+            example_tests(list, 2)
+        );
+    }
+
+    #[test]
+    fn test_lints() {
+        let message = |level: &str, rendered: &str| {
+            serde_json::json!({
+                "reason": "compiler-message",
+                "message": { "level": level, "rendered": rendered },
+            })
+            .to_string()
+        };
+        assert_eq!(
+            Lints::Clean,
+            lints(r#"{"reason":"build-finished","success":true}"#, "")
+        );
+        let stdout = [
+            message(
+                "warning",
+                "warning: this `if` has identical blocks\n --> src/part1.rs:3:5",
+            ),
+            message("warning", "warning: unused variable: `x`"),
+            r#"{"reason":"build-finished","success":true}"#.to_string(),
+        ]
+        .join("\n");
+        assert_eq!(
+            Lints::Warnings(2, "warning: this `if` has identical blocks".to_string()),
+            lints(&stdout, "")
+        );
+        assert_eq!(
+            Lints::Broken("error[E0425]: cannot find value `x` in this scope".to_string()),
+            lints(
+                &message("error", "error[E0425]: cannot find value `x` in this scope"),
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn test_modified() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join("aoc-test-modified");
+        fs::create_dir_all(dir.join("src")).into_diagnostic()?;
+        fs::write(dir.join("src").join("lib.rs"), "").into_diagnostic()?;
+        let before = modified(&dir)?;
+        assert!(before.contains_key(&dir.join("src").join("lib.rs")));
+        assert_eq!(before, modified(&dir)?);
+
+        fs::write(dir.join("input1.txt"), "").into_diagnostic()?;
+        assert_ne!(before, modified(&dir)?);
+        fs::remove_file(dir.join("input1.txt")).into_diagnostic()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use crate::{days::Solution, generate::Generate, repl::Open};
+
+/// Everything the runner needs to know about one year's workspace.
+#[derive(Clone, Copy)]
+pub struct Year {
+    pub year: u16,
+    /// The workspace's root, where its `day-XX` crates keep their inputs.
+    pub dir: &'static str,
+    pub solutions: &'static [Solution],
+    /// Every day that has a crate, with the title of its puzzle.
+    pub titles: &'static [(u8, &'static str)],
+    /// Every day's `SOURCE_HASH`, only these days have their answers cached.
+    pub sources: &'static [(u8, &'static str)],
+    /// Only set for the years whose days can generate random inputs.
+    pub generate: Option<Generate>,
+    /// Only set for the years whose days can be queried with `aoc repl`.
+    pub repl: Option<Open>,
+}
+
+impl Year {
+    pub fn find(&self, day: u8, part: u8) -> Option<&'static Solution> {
+        self.solutions
+            .iter()
+            .find(|solution| solution.day == day && solution.part == part)
+    }
+
+    pub fn source_hash(&self, day: u8) -> Option<&'static str> {
+        self.sources
+            .iter()
+            .find(|&&(known, _)| known == day)
+            .map(|&(_, hash)| hash)
+    }
+
+    /// Where the crate of `day` is.
+    pub fn day_dir(&self, day: u8) -> PathBuf {
+        Path::new(self.dir).join(format!("day-{day:02}"))
+    }
+
+    /// Where the day crate keeps the puzzle input for `part`.
+    pub fn input_path(&self, day: u8, part: u8) -> PathBuf {
+        self.day_dir(day).join(format!("input{part}.txt"))
+    }
+}
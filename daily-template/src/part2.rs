@@ -0,0 +1,25 @@
+use crate::custom_error::AocError;
+
+#[tracing::instrument]
+pub fn process(
+    _input: &str,
+) -> miette::Result<String, AocError> {
+    todo!("day 01 - part 1");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use aoc_common::examples::Example;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_process(#[files("examples/part2-*.txt")] path: PathBuf) -> miette::Result<()> {
+        let example = Example::load(&path);
+        assert_eq!(example.answer, process(&example.input)?);
+        Ok(())
+    }
+}
\ No newline at end of file
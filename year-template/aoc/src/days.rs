@@ -0,0 +1,18 @@
+use aoc_common::{days::Solution, year::Year};
+
+pub const SOLUTIONS: &[Solution] = &[];
+
+pub const TITLES: &[(u8, &str)] = &[];
+
+pub const SOURCES: &[(u8, &str)] = &[];
+
+/// The days in this workspace, for the runner.
+pub const YEAR: Year = Year {
+    year: {{project-name}},
+    dir: concat!(env!("CARGO_MANIFEST_DIR"), "/.."),
+    solutions: SOLUTIONS,
+    titles: TITLES,
+    sources: SOURCES,
+    generate: None,
+    repl: None,
+};